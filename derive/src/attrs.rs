@@ -3,7 +3,7 @@ use std::{cell::RefCell, fmt::Display};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::{
-    DeriveInput, Ident, LitStr, PathSegment, Token, meta::ParseNestedMeta, parenthesized,
+    DeriveInput, Ident, LitInt, LitStr, PathSegment, Token, meta::ParseNestedMeta, parenthesized,
     punctuated::Punctuated, spanned::Spanned,
 };
 
@@ -25,6 +25,72 @@ pub enum ArithMode {
     Full,
 }
 
+#[derive(Default)]
+pub enum SerdeMode {
+    #[default]
+    Name,
+    Position,
+}
+
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    const ALL: [(&str, RenameRule); 8] = [
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.iter().find(|(n, _)| *n == s).map(|(_, r)| *r)
+    }
+
+    /// Applies the rule to a `PascalCase` variant name.
+    pub fn apply(self, variant: &str) -> String {
+        let snake = || {
+            let mut res = String::new();
+            for (i, c) in variant.char_indices() {
+                if i > 0 && c.is_uppercase() {
+                    res.push('_');
+                }
+                res.extend(c.to_lowercase());
+            }
+            res
+        };
+        match self {
+            RenameRule::Lower => variant.to_lowercase(),
+            RenameRule::Upper => variant.to_uppercase(),
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            RenameRule::Snake => snake(),
+            RenameRule::ScreamingSnake => snake().to_uppercase(),
+            RenameRule::Kebab => snake().replace('_', "-"),
+            RenameRule::ScreamingKebab => snake().replace('_', "-").to_uppercase(),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ErrorList {
     pub errors: RefCell<Option<syn::Error>>,
@@ -41,6 +107,10 @@ pub struct Attrs {
     pub whitelist_active: bool,
     pub bounds_checks_mode: BoundsChecksMode,
     pub arith_mode: ArithMode,
+    pub delta: Option<Ident>,
+    pub max: Option<LitInt>,
+    pub rename_all: Option<(Span, RenameRule)>,
+    pub serde_mode: SerdeMode,
 }
 
 impl ErrorList {
@@ -127,6 +197,10 @@ impl Attrs {
         let mut arith_compat_list = Vec::new();
         let mut bounds_checks_mode = BoundsChecksMode::default();
         let mut arith_mode = ArithMode::default();
+        let mut delta = None;
+        let mut max = None;
+        let mut rename_all = None;
+        let mut serde_mode = SerdeMode::default();
         for attr in &ast.attrs {
             if !attr.path().is_ident(INDEXLAND) {
                 continue;
@@ -172,6 +246,42 @@ impl Attrs {
                             ))),
                         }
                     }
+                    "delta" => {
+                        // e.g. #[indexland(delta = NodeDelta)]
+                        delta = Some(meta.value()?.parse()?);
+                    }
+                    "max" => {
+                        // e.g. #[indexland(max = 4095)]
+                        let literal: LitInt = meta.value()?.parse()?;
+                        if let Err(e) = literal.base10_parse::<usize>() {
+                            errs.push(e);
+                        }
+                        max = Some(literal);
+                    }
+                    "serde" => {
+                        // e.g. #[indexland(serde = "position")]
+                        let literal: LitStr = meta.value()?.parse()?;
+                        let value = literal.value();
+                        match &*value {
+                            "name" => serde_mode = SerdeMode::Name,
+                            "position" => serde_mode = SerdeMode::Position,
+                            _ => errs.push(meta.error(format!(
+                                r#"unknown serde mode "{value}", expected "name" or "position""#
+                            ))),
+                        }
+                    }
+                    "rename_all" => {
+                        // e.g. #[indexland(rename_all = "snake_case")]
+                        let literal: LitStr = meta.value()?.parse()?;
+                        let value = literal.value();
+                        match RenameRule::from_str(&value) {
+                            Some(rule) => rename_all = Some((literal.span(), rule)),
+                            None => errs.push(meta.error(format!(
+                                r#"unknown rename rule "{value}", expected one of {}"#,
+                                RenameRule::ALL.map(|(n, _)| format!("\"{n}\"")).join(", ")
+                            ))),
+                        }
+                    }
                     "omit" => {
                         // e.g. #[indexland(omit(Display))]
                         let omit;
@@ -275,6 +385,36 @@ impl Attrs {
             whitelist_active: first_whitelist.is_some(),
             bounds_checks_mode,
             arith_mode,
+            delta,
+            max,
+            rename_all,
+            serde_mode,
+        }
+    }
+}
+
+/// Parses `#[indexland(rename = "..")]` on an enum variant.
+pub fn variant_rename(variant: &syn::Variant, errs: &ErrorList) -> Option<String> {
+    let mut rename = None;
+    for attr in &variant.attrs {
+        if !attr.path().is_ident(INDEXLAND) {
+            continue;
+        }
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let literal: LitStr = meta.value()?.parse()?;
+                rename = Some(literal.value());
+            } else {
+                errs.push(meta.error(format!(
+                    "unknown {INDEXLAND} variant attribute {}",
+                    meta.path.to_token_stream()
+                )));
+            }
+            Ok(())
+        });
+        if let Err(e) = res {
+            errs.push(e);
         }
     }
+    rename
 }
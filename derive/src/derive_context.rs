@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 use std::collections::HashMap;
-use syn::{Generics, Ident};
+use syn::{Generics, Ident, parse_quote};
 
 use crate::{
     attrs::Attrs,
@@ -14,6 +14,12 @@ pub struct DeriveContextBase {
     pub name: Ident,
     pub generics: Generics,
     pub self_as_idx: TokenStream,
+    // `Idx` requires `'static`, so every type parameter gets that bound
+    pub impl_generics: TokenStream,
+    // like `impl_generics`, but without the angle brackets
+    pub impl_params: TokenStream,
+    pub ty_generics: TokenStream,
+    pub where_clause: TokenStream,
 }
 
 pub struct DeriveContext<C> {
@@ -39,15 +45,28 @@ impl<C> DeriveCatalogEntry<C> {
 }
 
 impl<C> DeriveContext<C> {
-    pub fn new(attrs: Attrs, name: Ident, generics: Generics, custom: C) -> Self {
+    pub fn new(attrs: Attrs, name: Ident, mut generics: Generics, custom: C) -> Self {
         let indexland = &attrs.indexland_path;
         let self_as_idx = quote! { <Self as #indexland::Idx> };
+        for param in generics.type_params_mut() {
+            param.bounds.push(parse_quote!('static));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let params = &generics.params;
+        let impl_params = quote! { #params };
+        let impl_generics = impl_generics.to_token_stream();
+        let ty_generics = ty_generics.to_token_stream();
+        let where_clause = where_clause.to_token_stream();
         Self {
             base: DeriveContextBase {
                 attrs,
                 name,
                 generics,
                 self_as_idx,
+                impl_generics,
+                impl_params,
+                ty_generics,
+                where_clause,
             },
             derivs_catalog: Default::default(),
             derivs_default: Default::default(),
@@ -134,19 +153,11 @@ impl<C> DeriveContext<C> {
         }
 
         for arith_compat in &self.base.attrs.arith_compat_list {
-            derivations.push(derive_arith_compat(
-                &self.base.attrs.indexland_path,
-                &self.base.name,
-                arith_compat,
-            ));
+            derivations.push(derive_arith_compat(&self.base, arith_compat));
         }
 
         for idx_compat in &self.base.attrs.idx_compat_list {
-            derivations.push(derive_idx_compat(
-                &self.base.attrs.indexland_path,
-                &self.base.name,
-                idx_compat,
-            ));
+            derivations.push(derive_idx_compat(&self.base, idx_compat));
         }
 
         quote! {
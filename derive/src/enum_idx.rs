@@ -1,9 +1,9 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{ToTokens, quote};
-use syn::{Data, DeriveInput, Fields};
+use syn::{Data, DeriveInput, Fields, Visibility};
 
 use crate::{
-    attrs::{Attrs, BoundsChecksMode},
+    attrs::{Attrs, BoundsChecksMode, SerdeMode, variant_rename},
     derive_context::{DeriveContext, DeriveContextBase},
     shared_derives::{
         derive_add, derive_add_assign, derive_add_assign_compat, derive_add_compat, derive_clone,
@@ -20,6 +20,10 @@ use crate::{
 struct EnumCtxCustom<'a> {
     idents: Vec<&'a Ident>,
     ident_strings: Vec<String>,
+    // after applying `rename` / `rename_all`
+    names: Vec<String>,
+    vis: &'a Visibility,
+    repr: Ident,
 }
 
 type EnumCtx<'a> = DeriveContext<EnumCtxCustom<'a>>;
@@ -34,11 +38,14 @@ fn enum_derive_idx(ctx: &EnumCtx) -> TokenStream {
     let idents = &ctx.custom.idents;
     let count = idents.len();
     let var_zero = &idents[0];
-    let var_one = &idents[1];
+    // a single variant enum wraps around to itself
+    let var_one = idents.get(1).unwrap_or(var_zero);
     let var_max = &idents[count - 1];
 
     let indices_1 = 0..count;
     let indices_2 = 0..count;
+    let indices_3 = 0..count;
+    let indices_4 = 0..count;
 
     let panic_str = format!("index {{}} is out of bounds for {name}");
     let from_usize = match ctx.base.attrs.bounds_checks_mode {
@@ -75,13 +82,57 @@ fn enum_derive_idx(ctx: &EnumCtx) -> TokenStream {
         },
     };
 
+    let wrapping = if count == 1 {
+        quote! {
+            fn wrapping_add(self, _other: Self) -> Self {
+                #name::#var_zero
+            }
+
+            fn wrapping_sub(self, _other: Self) -> Self {
+                #name::#var_zero
+            }
+        }
+    } else {
+        quote! {
+            fn wrapping_add(self, other: Self) -> Self {
+                const COUNT: usize = #count;
+                let offset_on_wrap =
+                    (::core::primitive::usize::MAX % COUNT).saturating_add(1);
+                let (sum, of) = #self_as_idx::into_usize(self)
+                    .overflowing_add( #self_as_idx::into_usize(other));
+                if of {
+                    return #self_as_idx::from_usize(sum + offset_on_wrap);
+                }
+                if sum < COUNT {
+                    return #self_as_idx::from_usize(sum);
+                }
+                return #self_as_idx::from_usize(sum % COUNT);
+            }
+
+            fn wrapping_sub(self, other: Self) -> Self {
+                const COUNT: usize = #count;
+                let offset_on_wrap =
+                    (::core::primitive::usize::MAX % COUNT).saturating_add(1);
+                let (diff, of) = #self_as_idx::into_usize(self)
+                    .overflowing_sub(#self_as_idx::into_usize(other));
+                if of {
+                    return #self_as_idx::from_usize(diff - offset_on_wrap);
+                }
+                if diff < COUNT {
+                    return #self_as_idx::from_usize(diff);
+                }
+                #self_as_idx::from_usize(diff % COUNT)
+            }
+        }
+    };
+
     quote! {
         #[automatically_derived]
         impl #impl_generics #indexland::Idx for #name #ty_generics #where_clause {
             const ZERO: Self = #name::#var_zero;
             const ONE: Self = #name::#var_one;
             const MAX: Self = #name::#var_max;
-            const MAX_USIZE: usize = #count;
+            const MAX_USIZE: usize = #count - 1;
 
             #[inline(always)]
             fn from_usize_unchecked(v: usize) -> Self {
@@ -93,9 +144,24 @@ fn enum_derive_idx(ctx: &EnumCtx) -> TokenStream {
 
             #from_usize
 
+            #[inline]
+            fn try_from_usize(
+                v: usize,
+            ) -> ::core::result::Result<Self, #indexland::idx::IdxOutOfRange> {
+                match v {
+                    #(#indices_3 => ::core::result::Result::Ok(#name::#idents),)*
+                    _ => ::core::result::Result::Err(
+                        #indexland::idx::IdxOutOfRange::new::<Self>(v),
+                    ),
+                }
+            }
+
             #[inline(always)]
             fn into_usize_unchecked(self) -> usize  {
-                self as usize
+                // the position, which may differ from the discriminant
+                match self {
+                    #(#name::#idents => #indices_4,)*
+                }
             }
 
             #[inline(always)]
@@ -117,35 +183,7 @@ fn enum_derive_idx(ctx: &EnumCtx) -> TokenStream {
                 )
             }
 
-            fn wrapping_add(self, other: Self) -> Self {
-                const COUNT: usize = #count;
-                let offset_on_wrap =
-                    (::core::primitive::usize::MAX % COUNT).saturating_add(1);
-                let (sum, of) = #self_as_idx::into_usize(self)
-                    .overflowing_add( #self_as_idx::into_usize(other));
-                if of {
-                    return #self_as_idx::from_usize(sum + offset_on_wrap);
-                }
-                if sum < COUNT {
-                    return #self_as_idx::from_usize(sum);
-                }
-                return #self_as_idx::from_usize(sum % COUNT);
-            }
-
-            fn wrapping_sub(self, other: Self) -> Self {
-                const COUNT: usize = #count;
-                let offset_on_wrap =
-                    (::core::primitive::usize::MAX % COUNT).saturating_add(1);
-                let (diff, of) = #self_as_idx::into_usize(self)
-                    .overflowing_sub(#self_as_idx::into_usize(other));
-                if of {
-                    return #self_as_idx::from_usize(diff - offset_on_wrap);
-                }
-                if diff < COUNT {
-                    return #self_as_idx::from_usize(diff);
-                }
-                #self_as_idx::from_usize(diff % COUNT)
-            }
+            #wrapping
         }
     }
 }
@@ -155,6 +193,7 @@ fn enum_derive_idx_enum(ctx: &EnumCtx) -> TokenStream {
     let name = &ctx.base.name;
     let (impl_generics, ty_generics, where_clause) = ctx.base.generics.split_for_impl();
     let idents = &ctx.custom.idents;
+    let names = &ctx.custom.names;
     let count = idents.len();
     quote! {
         #[automatically_derived]
@@ -162,6 +201,157 @@ fn enum_derive_idx_enum(ctx: &EnumCtx) -> TokenStream {
             const VARIANT_COUNT: usize = #count;
             type EnumIndexArray<T> = #indexland::index_array::IndexArray<Self, T, #count>;
             const VARIANTS: &'static [Self] = &[ #(#name::#idents),* ];
+            const VARIANT_NAMES: &'static [&'static str] = &[ #(#names),* ];
+        }
+    }
+}
+
+fn enum_derive_repr_conversions(ctx: &EnumCtx) -> TokenStream {
+    let name = &ctx.base.name;
+    let (impl_generics, ty_generics, where_clause) = ctx.base.generics.split_for_impl();
+    let idents = &ctx.custom.idents;
+    let vis = ctx.custom.vis;
+    let repr = &ctx.custom.repr;
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The variant with the discriminant `v`, if any.
+            #vis const fn from_repr(v: #repr) -> ::core::option::Option<Self> {
+                #(
+                    if v == #name::#idents as #repr {
+                        return ::core::option::Option::Some(#name::#idents);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+            /// The discriminant of this variant. Unlike `Idx::into_usize`,
+            /// this is not necessarily its position.
+            #vis const fn to_repr(self) -> #repr {
+                self as #repr
+            }
+        }
+    }
+}
+
+fn enum_derive_const_conversions(ctx: &EnumCtx) -> TokenStream {
+    let name = &ctx.base.name;
+    let (impl_generics, ty_generics, where_clause) = ctx.base.generics.split_for_impl();
+    let idents = &ctx.custom.idents;
+    let vis = ctx.custom.vis;
+    let indices_1 = 0..idents.len();
+    let indices_2 = 0..idents.len();
+    let panic_str = format!("index out of bounds for {name}");
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Const alternative to `Idx::from_usize`.
+            #vis const fn from_usize_const(v: usize) -> Self {
+                match v {
+                    #(#indices_1 => #name::#idents,)*
+                    _ => ::core::panic!(#panic_str),
+                }
+            }
+            /// Const alternative to `Idx::into_usize`.
+            #vis const fn into_usize_const(self) -> usize {
+                match self {
+                    #(#name::#idents => #indices_2,)*
+                }
+            }
+        }
+    }
+}
+
+fn enum_derive_serialize(ctx: &EnumCtx) -> TokenStream {
+    let indexland = &ctx.base.attrs.indexland_path;
+    let self_as_idx = &ctx.base.self_as_idx;
+    let name = &ctx.base.name;
+    let idents = &ctx.custom.idents;
+    let names = &ctx.custom.names;
+    let body = match ctx.base.attrs.serde_mode {
+        SerdeMode::Name => quote! {
+            serializer.serialize_str(match self {
+                #(#name::#idents => #names,)*
+            })
+        },
+        SerdeMode::Position => quote! {
+            serializer.serialize_u64(#self_as_idx::into_usize(*self) as u64)
+        },
+    };
+    quote! {
+        #[automatically_derived]
+        impl #indexland::__private::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #indexland::__private::serde::Serializer,
+            {
+                #body
+            }
+        }
+    }
+}
+
+fn enum_derive_deserialize(ctx: &EnumCtx) -> TokenStream {
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let idents = &ctx.custom.idents;
+    let names = &ctx.custom.names;
+    let serde = quote!(#indexland::__private::serde);
+    let (expecting, visit, deserialize) = match ctx.base.attrs.serde_mode {
+        SerdeMode::Name => (
+            format!("a variant name of {name}"),
+            quote! {
+                fn visit_str<E>(self, v: &str) -> ::core::result::Result<#name, E>
+                where
+                    E: #serde::de::Error,
+                {
+                    match v {
+                        #(#names => ::core::result::Result::Ok(#name::#idents),)*
+                        _ => ::core::result::Result::Err(E::unknown_variant(v, &[#(#names),*])),
+                    }
+                }
+            },
+            quote!(deserialize_str),
+        ),
+        SerdeMode::Position => (
+            format!("a variant position of {name}"),
+            quote! {
+                fn visit_u64<E>(self, v: u64) -> ::core::result::Result<#name, E>
+                where
+                    E: #serde::de::Error,
+                {
+                    let pos = ::core::result::Result::map_err(
+                        usize::try_from(v),
+                        E::custom,
+                    )?;
+                    ::core::result::Result::map_err(
+                        <#name as #indexland::Idx>::try_from_usize(pos),
+                        E::custom,
+                    )
+                }
+            },
+            quote!(deserialize_u64),
+        ),
+    };
+    quote! {
+        #[automatically_derived]
+        impl<'de> #serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #serde::Deserializer<'de>,
+            {
+                struct Visitor;
+                impl #serde::de::Visitor<'_> for Visitor {
+                    type Value = #name;
+                    fn expecting(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+                    #visit
+                }
+                deserializer.#deserialize(Visitor)
+            }
         }
     }
 }
@@ -197,13 +387,32 @@ fn enum_derive_debug(ctx: &EnumCtx) -> TokenStream {
 fn enum_derive_display(ctx: &EnumCtx) -> TokenStream {
     let name = &ctx.base.name;
     let idents = &ctx.custom.idents;
-    let ident_strings = &ctx.custom.ident_strings;
+    let names = &ctx.custom.names;
     quote! {
         #[automatically_derived]
         impl ::core::fmt::Display for #name {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 match self {
-                    #(#name::#idents => f.write_str(#ident_strings)),*
+                    #(#name::#idents => f.write_str(#names)),*
+                }
+            }
+        }
+    }
+}
+
+fn enum_derive_from_str(ctx: &EnumCtx) -> TokenStream {
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let idents = &ctx.custom.idents;
+    let names = &ctx.custom.names;
+    quote! {
+        #[automatically_derived]
+        impl ::core::str::FromStr for #name {
+            type Err = #indexland::ParseIdxEnumError;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match s {
+                    #(#names => ::core::result::Result::Ok(#name::#idents),)*
+                    _ => ::core::result::Result::Err(#indexland::ParseIdxEnumError::new::<Self>()),
                 }
             }
         }
@@ -232,6 +441,11 @@ fn fill_derivation_list(ctx: &mut EnumCtx, rich_defaults: bool) {
     ctx.add_deriv_custom(true, "IdxEnum", enum_derive_idx_enum);
     ctx.add_deriv_custom(rich_defaults, "Debug", enum_derive_debug);
     ctx.add_deriv_custom(false, "Display", enum_derive_display);
+    ctx.add_deriv_custom(false, "FromStr", enum_derive_from_str);
+    ctx.add_deriv_custom(false, "Repr", enum_derive_repr_conversions);
+    ctx.add_deriv_custom(false, "Const", enum_derive_const_conversions);
+    ctx.add_deriv_custom(false, "Serialize", enum_derive_serialize);
+    ctx.add_deriv_custom(false, "Deserialize", enum_derive_deserialize);
     ctx.add_deriv_shared(rich_defaults, "Default", derive_default);
     ctx.add_deriv_shared(rich_defaults, "Clone", derive_clone);
     ctx.add_deriv_shared(rich_defaults, "Copy", derive_copy);
@@ -301,8 +515,20 @@ pub fn derive_idx_enum_inner(
     let name = ast.ident;
     let generics = ast.generics;
 
+    if let Some(max) = &attrs.max {
+        attrs
+            .error_list
+            .error_spanned(max, "max is only supported for newtype structs");
+    }
+    if let Some(delta) = &attrs.delta {
+        attrs
+            .error_list
+            .error_spanned(delta, "delta types are only supported for newtype structs");
+    }
+
     let mut idents = Vec::new();
     let mut ident_strings = Vec::new();
+    let mut names = Vec::<String>::new();
 
     for variant in &enum_data.variants {
         if !matches!(variant.fields, Fields::Unit) {
@@ -311,18 +537,31 @@ pub fn derive_idx_enum_inner(
                 "This macro does not support enum variants with payload.",
             ));
         };
+        let ident_string = variant.ident.to_string();
+        let name =
+            variant_rename(variant, &attrs.error_list).unwrap_or_else(|| match attrs.rename_all {
+                Some((_, rule)) => rule.apply(&ident_string),
+                None => ident_string.clone(),
+            });
+        if names.contains(&name) {
+            attrs
+                .error_list
+                .error_spanned(&variant.ident, format!("duplicate variant name \"{name}\""));
+        }
         idents.push(&variant.ident);
-        ident_strings.push(variant.ident.to_string());
+        ident_strings.push(ident_string);
+        names.push(name);
     }
 
-    let count = idents.len();
-    if count < 2 {
+    if idents.is_empty() {
         return Err(syn::Error::new(
             Span::call_site(),
-            "enum deriving IdxEnum must have at least two variants",
+            "enum deriving Idx must have at least one variant to serve as `Idx::ZERO`",
         ));
     }
 
+    let repr = repr_type(&ast.attrs).unwrap_or_else(|| Ident::new("isize", Span::call_site()));
+
     // we don't start generation if the type is already borked
     attrs.error_list.check()?;
 
@@ -333,6 +572,9 @@ pub fn derive_idx_enum_inner(
         EnumCtxCustom {
             idents,
             ident_strings,
+            names,
+            vis: &ast.vis,
+            repr,
         },
     );
 
@@ -344,3 +586,23 @@ pub fn derive_idx_enum_inner(
 
     Ok(output)
 }
+
+/// The integer type of `#[repr(..)]`, if specified.
+fn repr_type(attrs: &[syn::Attribute]) -> Option<Ident> {
+    const INT_TYPES: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    let mut repr = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        // other reprs like `C` are irrelevant here, and rustc reports errors
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INT_TYPES.contains(&&*ident.to_string()) {
+                    repr = Some(ident.clone());
+                }
+            }
+            Ok(())
+        });
+    }
+    repr
+}
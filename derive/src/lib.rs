@@ -63,6 +63,11 @@ pub fn derive_idx_newtype(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 /// For oppinionated defaults, use [`#[derive[Idx]]`](crate::Idx) instead,
 /// which derives all required supertraits aswell as arithmetic etc. for you.
 ///
+/// Variants are indexed by their position in the declaration, independent of
+/// explicit discriminants. With `#[indexland(extra(Repr))]`, inherent
+/// `from_repr` and `to_repr` methods are generated that convert from and to
+/// the discriminant, using the `#[repr(..)]` type or `isize`.
+///
 /// # Example
 /// ```
 /// use indexland::IdxEnum;
@@ -117,6 +122,11 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 ///
 /// # Opt-in Extra Traits (see [`#[indexland(arith_compat(T))]`](Idx#indexlandarith_compatt) and [`#[indexland(extra(..))]`](Idx#indexlandextra))
 /// - [`Display`](core::fmt::Display) (for enums, structs have this enabled by default)
+/// - [`FromStr`](core::str::FromStr) (for enums)
+/// - `Repr`: inherent `from_repr` and `to_repr` (for enums, see
+///   [`IdxEnum`](crate::IdxEnum))
+/// - `Const`: inherent `from_usize_const` and `into_usize_const` (see
+///   [Const Conversions](#const-conversions))
 /// - [`Mul`](core::ops::Mul) + [`MulAssign`](core::ops::MulAssign)
 /// - [`Div`](core::ops::Div) + [`DivAssign`](core::ops::DivAssign)
 /// - [`Rem`](core::ops::Rem) + [`RemAssign`](core::ops::RemAssign)
@@ -136,6 +146,33 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// };
 /// ```
 ///
+/// # Const Conversions
+/// With `#[indexland(extra(Const))]`, inherent `const fn from_usize_const`
+/// and `into_usize_const` are generated. This is supported for enums and for
+/// newtypes over integer primitives, [`NonZero`](core::num::NonZero) or
+/// `indexland::BoundedIdx`. They allow building `const` tables through
+/// `index_array![NodeId; ..]` and `IndexArray::index_const`.
+///
+/// # Generic Newtypes
+/// Besides the base type, newtypes may contain any number of
+/// [`PhantomData`](core::marker::PhantomData) markers. The generated impls
+/// only require `'static` of the type parameters, so the marker types don't
+/// have to implement `Copy`, `Ord`, etc.
+/// For the common case, `indexland::TypedId<T, Base = u32>` is provided.
+///
+/// ```
+/// use indexland::{Idx, IndexArray};
+/// use core::marker::PhantomData;
+///
+/// #[derive(Idx)]
+/// struct Id<T>(u32, PhantomData<fn() -> T>);
+///
+/// struct Node;
+///
+/// let names = IndexArray::<Id<Node>, &str, 2>::new(["a", "b"]);
+/// assert_eq!(names[Id::ONE], "b");
+/// ```
+///
 /// # Attributes
 ///
 ///
@@ -207,6 +244,57 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// assert_eq!(format!("{}", Status::Active), "Active");
 /// ```
 ///
+/// ## `#[indexland(rename_all = "..")]` and `#[indexland(rename = "..")]`
+/// Change the variant names of an enum, as used by
+/// [`IdxEnum::VARIANT_NAMES`](https://docs.rs/indexland/latest/indexland/trait.IdxEnum.html),
+/// [`Display`](core::fmt::Display) and [`FromStr`](core::str::FromStr).
+/// `rename_all` accepts the same rules as serde: `"lowercase"`, `"UPPERCASE"`,
+/// `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
+/// `"kebab-case"`, and `"SCREAMING-KEBAB-CASE"`.
+///
+/// ### Example
+/// ```
+/// use indexland::{Idx, IdxEnum};
+///
+/// #[derive(Idx)]
+/// #[indexland(rename_all = "snake_case", extra(Display, FromStr))]
+/// enum Stage {
+///     ParseInput,
+///     #[indexland(rename = "check")]
+///     TypeCheck,
+/// }
+///
+/// assert_eq!(Stage::VARIANT_NAMES, ["parse_input", "check"]);
+/// assert_eq!(Stage::TypeCheck.to_string(), "check");
+/// assert_eq!("parse_input".parse(), Ok(Stage::ParseInput));
+/// ```
+///
+/// ## `#[indexland(extra(Serialize, Deserialize))]`
+/// Implement serde's `Serialize` and `Deserialize`. Requires the `serde`
+/// feature of indexland.
+/// Newtypes serialize transparently and check the range of the index when
+/// deserializing. Enums serialize as their variant name, or as their
+/// position with `#[indexland(serde = "position")]`.
+///
+/// ### Example
+/// ```
+/// use indexland::Idx;
+///
+/// #[derive(Idx)]
+/// #[indexland(extra(Serialize, Deserialize))]
+/// struct NodeId(u32);
+///
+/// #[derive(Idx)]
+/// #[indexland(rename_all = "lowercase", extra(Serialize, Deserialize))]
+/// enum Axis {
+///     X,
+///     Y,
+/// }
+///
+/// let json = serde_json::to_string(&(NodeId(3), Axis::Y)).unwrap();
+/// assert_eq!(json, r#"[3,"y"]"#);
+/// ```
+///
 /// ## `#[indexland(omit(..))]`
 /// Suppress the derivation of certain traits (blacklist).
 ///
@@ -267,14 +355,14 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 ///
 /// ### Example
 /// ```
-/// use indexland::{Idx, IndexVec};
+/// use indexland::{Idx, IndexArray};
 ///
 /// #[derive(Idx)]
 /// #[indexland(idx_compat(usize))]
 /// struct ItemId(usize);
 ///
 /// // Can use usize directly to index containers
-/// let items = IndexVec::<ItemId, u8>::from_iter(0..10);
+/// let items = IndexArray::<ItemId, u8, 3>::new([0, 1, 2]);
 /// let item_0 = items[0]; // Allowed due to `idx_compat(usize)`.
 /// let item_1 = items[ItemId(1)]; // Still works.
 /// ```
@@ -284,13 +372,13 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 ///
 /// ### Example
 /// ```
-/// use indexland::{Idx, IndexVec};
+/// use indexland::{Idx, IndexArray};
 ///
 /// #[derive(Idx)]
 /// #[indexland(compat(usize))]
 /// struct FuzzyId(usize);
 ///
-/// let items = IndexVec::<FuzzyId, u8>::from_iter(0..10);
+/// let items = IndexArray::<FuzzyId, u8, 10>::new([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 /// let id = FuzzyId(5);
 ///
 /// // Both arithmetic and indexing work with usize
@@ -298,6 +386,51 @@ pub fn derive_idx_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// let item = items[7]; // idx_compat
 /// ```
 ///
+/// ## `#[indexland(max = N)]`
+/// Limit the index to `0..=N`, for ids that are bounded below the range of
+/// their base type. [`MAX`](Idx) and `MAX_USIZE` reflect the limit, and
+/// conversions from `usize` check it according to
+/// [`bounds_checks`](Idx#indexlandbounds_checks--).
+/// `IndexVec::push_get_idx` always refuses to grow past the limit.
+/// The base type must be an integer primitive, [`NonZero`](core::num::NonZero)
+/// or `indexland::BoundedIdx`.
+///
+/// ### Example
+/// ```
+/// use indexland::Idx;
+///
+/// #[derive(Idx)]
+/// #[indexland(max = 4095)]
+/// struct ChannelId(u16);
+///
+/// assert_eq!(ChannelId::MAX, ChannelId(4095));
+/// assert!(ChannelId::try_from_usize(4096).is_err());
+/// assert_eq!(ChannelId(4000).checked_add(ChannelId(100)), None);
+/// ```
+///
+/// ## `#[indexland(delta = ..)]`
+/// Generate a companion newtype around `isize` that represents the signed
+/// offset between two indices. `Self - Self` then yields the delta instead
+/// of `Self`, and [`Add<Delta>`](core::ops::Add) +
+/// [`Sub<Delta>`](core::ops::Sub) move an index by it, checked according to
+/// [`bounds_checks`](Idx#indexlandbounds_checks--).
+///
+/// ### Example
+/// ```
+/// use indexland::{Idx, IndexArray};
+///
+/// #[derive(Idx)]
+/// #[indexland(delta = NodeDelta)]
+/// struct NodeId(u32);
+///
+/// let delta = NodeId(5) - NodeId(7);
+/// assert_eq!(delta, NodeDelta(-2));
+/// assert_eq!(NodeId(7) + delta, NodeId(5));
+///
+/// let nodes = IndexArray::<NodeId, char, 3>::new(['a', 'b', 'c']);
+/// assert_eq!(nodes.get_relative(NodeId(2), delta), Some(&'a'));
+/// ```
+///
 /// ### `#[indexland(crate = ..)]`
 /// Change the crate name used within the derive macro,
 /// similar to the way [serde does this](https://serde.rs/container-attrs.html#crate).
@@ -1,6 +1,6 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Literal, Span, TokenStream};
 use quote::{ToTokens, quote};
-use syn::{Data, DeriveInput, Fields, Type};
+use syn::{Data, DeriveInput, Fields, Type, Visibility};
 
 use crate::{
     attrs::{Attrs, BoundsChecksMode},
@@ -18,28 +18,52 @@ use crate::{
 struct NewtypeCtxCustom<'a> {
     base_type: &'a Type,
     base_as_idx: TokenStream,
+    vis: &'a Visibility,
+    field_vis: &'a Visibility,
+    field: syn::Index,
+    // `, 1: ::core::marker::PhantomData` for every marker field
+    phantom_fields: TokenStream,
+    // `NonZero` bases store index `n` as `n + 1`
+    nonzero: bool,
 }
 
 type NewtypeCtx<'a> = DeriveContext<NewtypeCtxCustom<'a>>;
 
 fn newtype_derive_idx(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let indexland = &ctx.base.attrs.indexland_path;
     let name = &ctx.base.name;
 
-    let (impl_generics, ty_generics, where_clause) = ctx.base.generics.split_for_impl();
-
     let base_as_idx = &ctx.custom.base_as_idx;
 
+    let max = ctx
+        .base
+        .attrs
+        .max
+        .as_ref()
+        .map(|max| Literal::usize_unsuffixed(max.base10_parse().unwrap()));
+    let max_check = max.as_ref().map(|max| {
+        quote! {
+            if v > #max {
+                ::core::panic!("{}", #indexland::idx::IdxOutOfRange::new::<Self>(v));
+            }
+        }
+    });
+
     let checked_conversions = match ctx.base.attrs.bounds_checks_mode {
         BoundsChecksMode::Never => {
             quote! {
                 #[inline(always)]
                 fn from_usize(v: usize) -> Self {
-                    #name(#base_as_idx::from_usize_unchecked(v))
+                    #name { #field: #base_as_idx::from_usize_unchecked(v) #phantom_fields }
                 }
                 #[inline(always)]
                 fn into_usize(self) -> usize {
-                    #base_as_idx::into_usize_unchecked(self.0)
+                    #base_as_idx::into_usize_unchecked(self.#field)
                 }
             }
         }
@@ -48,18 +72,21 @@ fn newtype_derive_idx(ctx: &NewtypeCtx) -> TokenStream {
                 #[inline(always)]
                 fn from_usize(v: usize) -> Self {
                     #[cfg(debug_assertions)]
-                    return #name(#base_as_idx::from_usize(v));
+                    return {
+                        #max_check
+                        #name { #field: #base_as_idx::from_usize(v) #phantom_fields }
+                    };
 
                     #[cfg(not(debug_assertions))]
-                    #name(#base_as_idx::from_usize_unchecked(v))
+                    #name { #field: #base_as_idx::from_usize_unchecked(v) #phantom_fields }
                 }
                 #[inline(always)]
                 fn into_usize(self) -> usize {
                     #[cfg(debug_assertions)]
-                    return #base_as_idx::into_usize(self.0);
+                    return #base_as_idx::into_usize(self.#field);
 
                     #[cfg(not(debug_assertions))]
-                    #base_as_idx::into_usize_unchecked(self.0)
+                    #base_as_idx::into_usize_unchecked(self.#field)
                 }
             }
         }
@@ -67,53 +94,141 @@ fn newtype_derive_idx(ctx: &NewtypeCtx) -> TokenStream {
             quote! {
                 #[inline(always)]
                 fn from_usize(v: usize) -> Self {
-                    #name(#base_as_idx::from_usize(v))
+                    #max_check
+                    #name { #field: #base_as_idx::from_usize(v) #phantom_fields }
                 }
                 #[inline(always)]
                 fn into_usize(self) -> usize {
-                    #base_as_idx::into_usize(self.0)
+                    #base_as_idx::into_usize(self.#field)
                 }
             }
         }
     };
 
+    if let Some(max) = &ctx.base.attrs.max {
+        if !supports_const_conversions(ctx.custom.base_type) {
+            ctx.base.attrs.error_list.error_spanned(
+                max,
+                "`max` requires an integer primitive, `NonZero` or `BoundedIdx` base type",
+            );
+        }
+    }
+    let base_type = ctx.custom.base_type;
+
+    let (max_consts, arith) = match &max {
+        Some(max) => (
+            quote! {
+                const MAX: Self = #name {
+                    #field: #indexland::idx::IdxConst::<#base_type>::from_usize(#max)
+                    #phantom_fields
+                };
+                const MAX_USIZE: usize = {
+                    ::core::assert!(
+                        #max <= #base_as_idx::MAX_USIZE,
+                        "max exceeds the range of the base type"
+                    );
+                    #max
+                };
+            },
+            quote! {
+                fn wrapping_add(self, other: Self) -> Self {
+                    let (a, b) = (Self::into_usize(self), Self::into_usize(other));
+                    let room = #max - a;
+                    Self::from_usize_unchecked(if b <= room { a + b } else { b - room - 1 })
+                }
+                fn wrapping_sub(self, other: Self) -> Self {
+                    let (a, b) = (Self::into_usize(self), Self::into_usize(other));
+                    Self::from_usize_unchecked(if b <= a { a - b } else { #max - (b - a - 1) })
+                }
+                fn try_from_usize(
+                    v: usize,
+                ) -> ::core::result::Result<Self, #indexland::idx::IdxOutOfRange> {
+                    if v > #max {
+                        return ::core::result::Result::Err(
+                            #indexland::idx::IdxOutOfRange::new::<Self>(v),
+                        );
+                    }
+                    ::core::result::Result::Ok(Self::from_usize_unchecked(v))
+                }
+            },
+        ),
+        None => (
+            quote! {
+                const MAX: Self = #name { #field: #base_as_idx::MAX #phantom_fields };
+                const MAX_USIZE: usize = #base_as_idx::MAX_USIZE;
+            },
+            quote! {
+                fn wrapping_add(self, other: Self) -> Self {
+                    #name { #field: #base_as_idx::wrapping_add(self.#field, other.#field) #phantom_fields }
+                }
+                fn wrapping_sub(self, other: Self) -> Self {
+                    #name { #field: #base_as_idx::wrapping_sub(self.#field, other.#field) #phantom_fields }
+                }
+                fn saturating_add(self, other: Self) -> Self {
+                    #name { #field: #base_as_idx::saturating_add(self.#field, other.#field) #phantom_fields }
+                }
+                fn saturating_sub(self, other: Self) -> Self {
+                    #name { #field: #base_as_idx::saturating_sub(self.#field, other.#field) #phantom_fields }
+                }
+                fn try_from_usize(
+                    v: usize,
+                ) -> ::core::result::Result<Self, #indexland::idx::IdxOutOfRange> {
+                    match #base_as_idx::try_from_usize(v) {
+                        ::core::result::Result::Ok(v) => ::core::result::Result::Ok(#name { #field: v #phantom_fields }),
+                        ::core::result::Result::Err(_) => ::core::result::Result::Err(
+                            #indexland::idx::IdxOutOfRange::new::<Self>(v),
+                        ),
+                    }
+                }
+                fn checked_add(self, other: Self) -> ::core::option::Option<Self> {
+                    ::core::option::Option::map(#base_as_idx::checked_add(self.#field, other.#field), |v| #name { #field: v #phantom_fields })
+                }
+                fn checked_sub(self, other: Self) -> ::core::option::Option<Self> {
+                    ::core::option::Option::map(#base_as_idx::checked_sub(self.#field, other.#field), |v| #name { #field: v #phantom_fields })
+                }
+                fn checked_offset(self, offset: isize) -> ::core::option::Option<Self> {
+                    ::core::option::Option::map(#base_as_idx::checked_offset(self.#field, offset), |v| #name { #field: v #phantom_fields })
+                }
+                fn distance(self, other: Self) -> usize {
+                    #base_as_idx::distance(self.#field, other.#field)
+                }
+            },
+        ),
+    };
+
     quote! {
         #[automatically_derived]
         impl #impl_generics #indexland::Idx for #name #ty_generics #where_clause {
-            const ZERO: Self = #name(#base_as_idx::ZERO);
-            const ONE: Self = #name(#base_as_idx::ONE);
-            const MAX: Self = #name(#base_as_idx::MAX);
-            const MAX_USIZE: usize = #base_as_idx::MAX_USIZE;
+            const ZERO: Self = #name { #field: #base_as_idx::ZERO #phantom_fields };
+            const ONE: Self = #name { #field: #base_as_idx::ONE #phantom_fields };
+            #max_consts
 
             #checked_conversions
             #[inline(always)]
             fn from_usize_unchecked(v: usize) -> Self {
-                #name(#base_as_idx::from_usize_unchecked(v))
+                #name { #field: #base_as_idx::from_usize_unchecked(v) #phantom_fields }
             }
             #[inline(always)]
             fn into_usize_unchecked(self) -> usize {
-                #base_as_idx::into_usize_unchecked(self.0)
+                #base_as_idx::into_usize_unchecked(self.#field)
             }
-            fn wrapping_add(self, other: Self) -> Self {
-                #name(#base_as_idx::wrapping_add(self.0, other.0))
-            }
-            fn wrapping_sub(self, other: Self) -> Self {
-                #name(#base_as_idx::wrapping_sub(self.0, other.0))
-            }
-            fn saturating_add(self, other: Self) -> Self {
-                #name(#base_as_idx::saturating_add(self.0, other.0))
-            }
-            fn saturating_sub(self, other: Self) -> Self {
-                #name(#base_as_idx::saturating_sub(self.0, other.0))
+            #[inline(always)]
+            fn try_into_usize(self) -> ::core::option::Option<usize> {
+                #base_as_idx::try_into_usize(self.#field)
             }
+            #arith
         }
     }
 }
 
 fn newtype_derive_idx_newtype(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let indexland = &ctx.base.attrs.indexland_path;
     let name = &ctx.base.name;
-    let (impl_generics, ty_generics, where_clause) = ctx.base.generics.split_for_impl();
     let base_type = &ctx.custom.base_type;
     quote! {
         #[automatically_derived]
@@ -121,24 +236,28 @@ fn newtype_derive_idx_newtype(ctx: &NewtypeCtx) -> TokenStream {
             type Base = #base_type;
             #[inline]
             fn new(v: #base_type) -> Self {
-                #name(v)
+                #name { #field: v #phantom_fields }
             }
             #[inline]
             fn into_inner(self) -> #base_type {
-                self.0
+                self.#field
             }
         }
     }
 }
 
 fn newtype_derive_hash(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
     quote! {
         #[automatically_derived]
-        impl ::core::hash::Hash for #name {
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
             #[inline]
             fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-                self.0.hash(state);
+                self.#field.hash(state);
             }
         }
 
@@ -146,155 +265,522 @@ fn newtype_derive_hash(ctx: &NewtypeCtx) -> TokenStream {
 }
 
 fn newtype_derive_from_usize(ctx: &NewtypeCtx) -> TokenStream {
-    let indexland = &ctx.base.attrs.indexland_path;
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let self_as_idx = &ctx.base.self_as_idx;
     let name = &ctx.base.name;
-    let base_type = &ctx.custom.base_type;
     quote! {
         #[automatically_derived]
-        impl ::core::convert::From<usize> for #name {
+        impl #impl_generics ::core::convert::From<usize> for #name #ty_generics #where_clause {
             #[inline]
-            fn from(v: usize) -> #name {
-                #name(<#base_type as #indexland::Idx>::from_usize(v))
+            fn from(v: usize) -> #name #ty_generics {
+                // goes through `Self` so `max` is respected
+                #self_as_idx::from_usize(v)
             }
         }
     }
 }
 
 fn newtype_derive_from_self_for_usize(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let indexland = &ctx.base.attrs.indexland_path;
     let name = &ctx.base.name;
     let base_type = &ctx.custom.base_type;
     quote! {
         #[automatically_derived]
-        impl ::core::convert::From<#name> for usize {
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for usize #where_clause {
             #[inline]
-            fn from(v: #name) -> usize {
-                <#base_type as #indexland::Idx>::into_usize(v.0)
+            fn from(v: #name #ty_generics) -> usize {
+                <#base_type as #indexland::Idx>::into_usize(v.#field)
+            }
+        }
+    }
+}
+
+fn newtype_derive_serialize(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #indexland::__private::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #indexland::__private::serde::Serializer,
+            {
+                #indexland::__private::serde::Serialize::serialize(&self.#field, serializer)
+            }
+        }
+    }
+}
+
+fn newtype_derive_deserialize(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_params = &ctx.base.impl_params;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let base_type = &ctx.custom.base_type;
+    let base_as_idx = &ctx.custom.base_as_idx;
+    quote! {
+        #[automatically_derived]
+        impl<'de, #impl_params> #indexland::__private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #indexland::__private::serde::Deserializer<'de>,
+            {
+                let base = <#base_type as #indexland::__private::serde::Deserialize<'de>>::deserialize(
+                    deserializer,
+                )?;
+                let ::core::option::Option::Some(v) = #base_as_idx::try_into_usize(base) else {
+                    return ::core::result::Result::Err(
+                        <D::Error as #indexland::__private::serde::de::Error>::custom(
+                            "index out of range",
+                        ),
+                    );
+                };
+                ::core::result::Result::map_err(
+                    <Self as #indexland::Idx>::try_from_usize(v),
+                    <D::Error as #indexland::__private::serde::de::Error>::custom,
+                )
             }
         }
     }
 }
 
 fn newtype_derive_debug(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
+    let indexland = &ctx.base.attrs.indexland_path;
+    // show the index rather than the stored `n + 1`
+    let value = if ctx.custom.nonzero {
+        quote!(#indexland::Idx::into_usize(*self))
+    } else {
+        quote!(self.#field)
+    };
     quote! {
         #[automatically_derived]
-        impl ::core::fmt::Debug for #name {
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Debug::fmt(&self.0, f)
+                core::fmt::Debug::fmt(&#value, f)
             }
         }
     }
 }
 
 fn newtype_derive_display(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
+    let indexland = &ctx.base.attrs.indexland_path;
+    // show the index rather than the stored `n + 1`
+    let value = if ctx.custom.nonzero {
+        quote!(#indexland::Idx::into_usize(*self))
+    } else {
+        quote!(self.#field)
+    };
     quote! {
         #[automatically_derived]
-        impl ::core::fmt::Display for #name {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                core::fmt::Display::fmt(&self.0, f)
+                core::fmt::Display::fmt(&#value, f)
             }
         }
     }
 }
 
 fn newtype_derive_add(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let name = &ctx.base.name;
+    if ctx.base.attrs.max.is_some() || ctx.custom.nonzero {
+        return newtype_derive_bounded_op(ctx, quote!(Add), quote!(add), quote!(+));
+    }
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Add for #name {
+        impl #impl_generics ::core::ops::Add for #name #ty_generics #where_clause {
             type Output = Self;
             fn add(self, rhs: Self) -> Self::Output {
-                #name(self.0 + rhs.0)
+                #name { #field: self.#field + rhs.#field #phantom_fields }
             }
         }
     }
 }
 
 fn newtype_derive_sub(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let name = &ctx.base.name;
+    if ctx.custom.nonzero {
+        return newtype_derive_bounded_op(ctx, quote!(Sub), quote!(sub), quote!(-));
+    }
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Sub for #name {
+        impl #impl_generics ::core::ops::Sub for #name #ty_generics #where_clause {
             type Output = Self;
             fn sub(self, rhs: Self) -> Self::Output {
-                #name(self.0 - rhs.0)
+                #name { #field: self.#field - rhs.#field #phantom_fields }
+            }
+        }
+    }
+}
+
+// goes through `from_usize` so the result is checked against `max`,
+// also used for bases like `NonZero` that don't implement the operator
+fn newtype_derive_bounded_op(
+    ctx: &NewtypeCtx,
+    op_trait: TokenStream,
+    op_fn: TokenStream,
+    op: TokenStream,
+) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op_trait for #name #ty_generics #where_clause {
+            type Output = Self;
+            fn #op_fn(self, rhs: Self) -> Self::Output {
+                #indexland::Idx::from_usize(
+                    #indexland::Idx::into_usize(self) #op #indexland::Idx::into_usize(rhs),
+                )
+            }
+        }
+    }
+}
+
+fn newtype_derive_sub_delta(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let delta = ctx.base.attrs.delta.as_ref().unwrap();
+    let checked = quote! {
+        #delta(::core::option::Option::expect(
+            #indexland::Idx::checked_delta(self, rhs),
+            "index delta out of range",
+        ))
+    };
+    let unchecked = quote! {
+        #delta(
+            #indexland::Idx::into_usize_unchecked(self)
+                .wrapping_sub(#indexland::Idx::into_usize_unchecked(rhs)) as isize,
+        )
+    };
+    let body = match ctx.base.attrs.bounds_checks_mode {
+        BoundsChecksMode::Never => unchecked,
+        BoundsChecksMode::DebugOnly => quote! {
+            #[cfg(debug_assertions)]
+            return #checked;
+
+            #[cfg(not(debug_assertions))]
+            #unchecked
+        },
+        BoundsChecksMode::Always => checked,
+    };
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Sub for #name #ty_generics #where_clause {
+            type Output = #delta;
+            fn sub(self, rhs: Self) -> #delta {
+                #body
+            }
+        }
+    }
+}
+
+fn newtype_derive_delta(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let vis = ctx.custom.vis;
+    let field_vis = ctx.custom.field_vis;
+    let delta = ctx.base.attrs.delta.as_ref().unwrap();
+    let doc = format!("A signed offset between two [`{name}`]s.");
+
+    let bounds_checked =
+        |checked: TokenStream, unchecked: TokenStream| match ctx.base.attrs.bounds_checks_mode {
+            BoundsChecksMode::Never => unchecked,
+            BoundsChecksMode::DebugOnly => quote! {
+                #[cfg(debug_assertions)]
+                return #checked;
+
+                #[cfg(not(debug_assertions))]
+                #unchecked
+            },
+            BoundsChecksMode::Always => checked,
+        };
+    let offset_unchecked = |rhs: TokenStream| {
+        quote! {
+            #indexland::Idx::from_usize_unchecked(
+                #indexland::Idx::into_usize_unchecked(self).wrapping_add_signed(#rhs),
+            )
+        }
+    };
+    let add = bounds_checked(
+        quote! {
+            ::core::option::Option::expect(
+                #indexland::Idx::checked_offset(self, rhs.0),
+                "index offset out of range",
+            )
+        },
+        offset_unchecked(quote!(rhs.0)),
+    );
+    // `isize::MIN` has no negation, so the checked path must not wrap it
+    // into a forward offset.
+    let sub = bounds_checked(
+        quote! {
+            ::core::option::Option::expect(
+                ::core::option::Option::and_then(
+                    rhs.0.checked_neg(),
+                    |offset| #indexland::Idx::checked_offset(self, offset),
+                ),
+                "index offset out of range",
+            )
+        },
+        offset_unchecked(quote!(rhs.0.wrapping_neg())),
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::default::Default,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::cmp::PartialOrd,
+            ::core::cmp::Ord,
+            ::core::hash::Hash,
+        )]
+        #vis struct #delta(#field_vis isize);
+
+        #[automatically_derived]
+        impl #indexland::IdxDelta for #delta {
+            const ZERO: Self = #delta(0);
+            #[inline(always)]
+            fn from_isize(v: isize) -> Self {
+                #delta(v)
+            }
+            #[inline(always)]
+            fn into_isize(self) -> isize {
+                self.0
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::fmt::Debug for #delta {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::fmt::Display for #delta {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::ops::Add for #delta {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                #delta(self.0 + rhs.0)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::ops::Sub for #delta {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                #delta(self.0 - rhs.0)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::ops::Neg for #delta {
+            type Output = Self;
+            fn neg(self) -> Self {
+                #delta(-self.0)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::ops::AddAssign for #delta {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::ops::SubAssign for #delta {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Add<#delta> for #name #ty_generics #where_clause {
+            type Output = Self;
+            fn add(self, rhs: #delta) -> Self {
+                #add
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Sub<#delta> for #name #ty_generics #where_clause {
+            type Output = Self;
+            fn sub(self, rhs: #delta) -> Self {
+                #sub
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::AddAssign<#delta> for #name #ty_generics #where_clause {
+            fn add_assign(&mut self, rhs: #delta) {
+                *self = *self + rhs;
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::SubAssign<#delta> for #name #ty_generics #where_clause {
+            fn sub_assign(&mut self, rhs: #delta) {
+                *self = *self - rhs;
             }
         }
     }
 }
 
 fn newtype_derive_mul(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let name = &ctx.base.name;
+    if ctx.base.attrs.max.is_some() || ctx.custom.nonzero {
+        return newtype_derive_bounded_op(ctx, quote!(Mul), quote!(mul), quote!(*));
+    }
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Mul for #name {
+        impl #impl_generics ::core::ops::Mul for #name #ty_generics #where_clause {
             type Output = Self;
             fn mul(self, rhs: Self) -> Self::Output {
-                #name(self.0 * rhs.0)
+                #name { #field: self.#field * rhs.#field #phantom_fields }
             }
         }
     }
 }
 
 fn newtype_derive_div(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let name = &ctx.base.name;
+    if ctx.custom.nonzero {
+        return newtype_derive_bounded_op(ctx, quote!(Div), quote!(div), quote!(/));
+    }
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Div for #name {
+        impl #impl_generics ::core::ops::Div for #name #ty_generics #where_clause {
             type Output = Self;
             fn div(self, rhs: Self) -> Self::Output {
-                #name(self.0 / rhs.0)
+                #name { #field: self.#field / rhs.#field #phantom_fields }
             }
         }
     }
 }
 
 fn newtype_derive_rem(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
     let name = &ctx.base.name;
+    if ctx.custom.nonzero {
+        return newtype_derive_bounded_op(ctx, quote!(Rem), quote!(rem), quote!(%));
+    }
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Rem for #name {
+        impl #impl_generics ::core::ops::Rem for #name #ty_generics #where_clause {
             type Output = Self;
             fn rem(self, rhs: Self) -> Self::Output {
-                #name(self.0 % rhs.0)
+                #name { #field: self.#field % rhs.#field #phantom_fields }
             }
         }
     }
 }
 
 fn newtype_derive_partial_ord(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::PartialOrd for #name {
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
             fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-                core::cmp::PartialOrd::partial_cmp(&self.0, &other.0)
+                core::cmp::PartialOrd::partial_cmp(&self.#field, &other.#field)
             }
         }
     }
 }
 
 fn newtype_derive_ord(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::Ord for #name {
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
             fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-                core::cmp::Ord::cmp(&self.0, &other.0)
+                core::cmp::Ord::cmp(&self.#field, &other.#field)
             }
         }
     }
 }
 
 fn newtype_derive_partial_eq(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
     let name = &ctx.base.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::PartialEq for #name {
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
             fn eq(&self, other: &Self) -> bool {
-                self.0 == other.0
+                self.#field == other.#field
             }
         }
     }
@@ -310,16 +796,26 @@ fn fill_derivation_list(ctx: &mut NewtypeCtx, rich_defaults: bool) {
     ctx.add_deriv_custom(true, "IdxEnum", newtype_derive_idx_newtype);
     ctx.add_deriv_custom(rich_defaults, "Debug", newtype_derive_debug);
     ctx.add_deriv_custom(rich_defaults, "Display", newtype_derive_display);
+    ctx.add_deriv_custom(false, "Serialize", newtype_derive_serialize);
+    ctx.add_deriv_custom(false, "Deserialize", newtype_derive_deserialize);
+    ctx.add_deriv_custom(false, "Const", newtype_derive_const_conversions);
     ctx.add_deriv_shared(rich_defaults, "Default", derive_default);
     ctx.add_deriv_shared(rich_defaults, "Clone", derive_clone);
     ctx.add_deriv_shared(rich_defaults, "Copy", derive_copy);
     ctx.add_deriv_custom(rich_defaults, "Add", newtype_derive_add);
-    ctx.add_deriv_custom(rich_defaults, "Sub", newtype_derive_sub);
+    if ctx.base.attrs.delta.is_some() {
+        // `Self - Self` yields the delta instead
+        ctx.add_deriv_custom(rich_defaults, "Sub", newtype_derive_sub_delta);
+    } else {
+        ctx.add_deriv_custom(rich_defaults, "Sub", newtype_derive_sub);
+    }
     ctx.add_deriv_custom(full_arith, "Mul", newtype_derive_mul);
     ctx.add_deriv_custom(full_arith, "Div", newtype_derive_div);
     ctx.add_deriv_custom(full_arith, "Rem", newtype_derive_rem);
     ctx.add_deriv_shared(rich_defaults, "AddAssign", derive_add_assign);
-    ctx.add_deriv_shared(rich_defaults, "SubAssign", derive_sub_assign);
+    if ctx.base.attrs.delta.is_none() {
+        ctx.add_deriv_shared(rich_defaults, "SubAssign", derive_sub_assign);
+    }
     ctx.add_deriv_shared(full_arith, "MulAssign", derive_mul_assign);
     ctx.add_deriv_shared(full_arith, "DivAssign", derive_div_assign);
     ctx.add_deriv_shared(full_arith, "RemAssign", derive_rem_assign);
@@ -363,6 +859,96 @@ fn fill_derivation_list(ctx: &mut NewtypeCtx, rich_defaults: bool) {
     }
 }
 
+fn newtype_derive_const_conversions(ctx: &NewtypeCtx) -> TokenStream {
+    let impl_generics = &ctx.base.impl_generics;
+    let ty_generics = &ctx.base.ty_generics;
+    let where_clause = &ctx.base.where_clause;
+    let field = &ctx.custom.field;
+    let phantom_fields = &ctx.custom.phantom_fields;
+    let indexland = &ctx.base.attrs.indexland_path;
+    let name = &ctx.base.name;
+    let base_type = ctx.custom.base_type;
+    let vis = ctx.custom.vis;
+    if !supports_const_conversions(base_type) {
+        ctx.base.attrs.error_list.error_spanned(
+            base_type,
+            "`Const` requires an integer primitive, `NonZero` or `BoundedIdx` base type",
+        );
+        return TokenStream::new();
+    }
+    let max_check = ctx.base.attrs.max.as_ref().map(|max| {
+        let max = Literal::usize_unsuffixed(max.base10_parse().unwrap());
+        let panic_str = format!("index out of range for {name}");
+        quote! {
+            if v > #max {
+                ::core::panic!(#panic_str);
+            }
+        }
+    });
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Const alternative to `Idx::from_usize`.
+            #vis const fn from_usize_const(v: usize) -> Self {
+                #max_check
+                #name {
+                    #field: #indexland::idx::IdxConst::<#base_type>::from_usize(v)
+                    #phantom_fields
+                }
+            }
+            /// Const alternative to `Idx::into_usize`.
+            #vis const fn into_usize_const(self) -> usize {
+                #indexland::idx::IdxConst::<#base_type>::into_usize(self.#field)
+            }
+        }
+    }
+}
+
+// base types with an `IdxConst` implementation
+fn supports_const_conversions(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|s| {
+        let ident = s.ident.to_string();
+        matches!(
+            ident.as_str(),
+            "u8" | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+                | "BoundedIdx"
+        ) || ident.starts_with("NonZero")
+    })
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|s| s.ident == "PhantomData")
+}
+
+fn is_nonzero(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|s| s.ident.to_string().starts_with("NonZero"))
+}
+
 pub fn derive_idx_newtype_inner(
     ast: DeriveInput,
     rich_defaults: bool,
@@ -373,24 +959,47 @@ pub fn derive_idx_newtype_inner(
             "This macro only supports newtype structs",
         ));
     };
-    let inner = match &struct_data.fields {
-        Fields::Unnamed(fields_unnamed) => {
-            if fields_unnamed.unnamed.len() != 1 {
-                return Err(syn::Error::new(
-                    Span::call_site(),
-                    "This macro only supports newtype structs with exactly one member",
-                ));
-            }
-            &fields_unnamed.unnamed[0]
+    let Fields::Unnamed(fields_unnamed) = &struct_data.fields else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "This macro only supports newtype structs",
+        ));
+    };
+    let mut inner = None;
+    let mut phantom_fields = TokenStream::new();
+    for (i, f) in fields_unnamed.unnamed.iter().enumerate() {
+        let index = syn::Index::from(i);
+        if is_phantom_data(&f.ty) {
+            phantom_fields.extend(quote! { , #index: ::core::marker::PhantomData });
+            continue;
         }
-        Fields::Named(_) | Fields::Unit => {
+        if inner.is_some() {
             return Err(syn::Error::new(
                 Span::call_site(),
-                "This macro only supports newtype structs",
+                "This macro only supports newtype structs with exactly one non-`PhantomData` member",
             ));
         }
+        inner = Some((index, f));
+    }
+    let Some((field, inner)) = inner else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "This macro only supports newtype structs with exactly one non-`PhantomData` member",
+        ));
     };
     let attrs = Attrs::from_input(&ast);
+    if let Some((span, _)) = attrs.rename_all {
+        attrs
+            .error_list
+            .error(span, "rename_all is only supported for enums");
+    }
+    if let Some(delta) = &attrs.delta {
+        if !ast.generics.params.is_empty() {
+            attrs
+                .error_list
+                .error(delta.span(), "delta is not supported for generic newtypes");
+        }
+    }
     let base_type = &inner.ty;
     let name = ast.ident;
     let indexland = &attrs.indexland_path;
@@ -398,6 +1007,11 @@ pub fn derive_idx_newtype_inner(
     let custom = NewtypeCtxCustom {
         base_type,
         base_as_idx: quote! { <#base_type as #indexland::Idx> },
+        vis: &ast.vis,
+        field_vis: &inner.vis,
+        field,
+        phantom_fields,
+        nonzero: is_nonzero(base_type),
     };
 
     let mut ctx = NewtypeCtx::new(attrs, name, ast.generics, custom);
@@ -407,7 +1021,10 @@ pub fn derive_idx_newtype_inner(
 
     fill_derivation_list(&mut ctx, rich_defaults);
 
-    let res = ctx.generate();
+    let mut res = ctx.generate();
+    if ctx.base.attrs.delta.is_some() {
+        res.extend(newtype_derive_delta(&ctx));
+    }
 
     ctx.base.attrs.error_list.check()?;
 
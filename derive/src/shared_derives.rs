@@ -3,44 +3,49 @@ use quote::quote;
 
 use crate::derive_context::DeriveContextBase;
 
-pub fn derive_idx_compat(
-    indexland: &syn::Path,
-    name: &syn::Ident,
-    compat: &syn::Path,
-) -> TokenStream {
+pub fn derive_idx_compat(ctx: &DeriveContextBase, compat: &syn::Path) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
+    let indexland = &ctx.attrs.indexland_path;
+    let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl #indexland::idx::IdxCompat<#name> for #compat {
+        impl #impl_generics #indexland::idx::IdxCompat<#name #ty_generics> for #compat #where_clause {
             #[inline(always)]
-            fn idx_cast(self) -> #name {
-                #name::from_usize(Idx::into_usize(self))
+            fn idx_cast(self) -> #name #ty_generics {
+                <#name #ty_generics as #indexland::Idx>::from_usize(Idx::into_usize(self))
             }
         }
     }
 }
 
-pub fn derive_arith_compat(
-    indexland: &syn::Path,
-    name: &syn::Ident,
-    compat: &syn::Path,
-) -> TokenStream {
+pub fn derive_arith_compat(ctx: &DeriveContextBase, compat: &syn::Path) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
+    let indexland = &ctx.attrs.indexland_path;
+    let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl #indexland::idx::ArithCompat<#name> for #compat {
+        impl #impl_generics #indexland::idx::ArithCompat<#name #ty_generics> for #compat #where_clause {
             #[inline(always)]
-            fn to_idx(self) -> #name {
-                #name::from_usize(Idx::into_usize(self))
+            fn to_idx(self) -> #name #ty_generics {
+                <#name #ty_generics as #indexland::Idx>::from_usize(Idx::into_usize(self))
             }
         }
     }
 }
 
 pub fn derive_default(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::default::Default for #name {
+        impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
             fn default() -> Self {
                 #indexland::Idx::ZERO
             }
@@ -49,10 +54,13 @@ pub fn derive_default(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_clone(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::clone::Clone for #name {
+        impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
             fn clone(&self) -> Self {
                *self
             }
@@ -61,18 +69,24 @@ pub fn derive_clone(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_copy(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::marker::Copy for #name {}
+        impl #impl_generics ::core::marker::Copy for #name #ty_generics #where_clause {}
     }
 }
 
 pub fn derive_add_assign(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::AddAssign for #name {
+        impl #impl_generics ::core::ops::AddAssign for #name #ty_generics #where_clause {
             fn add_assign(&mut self, rhs: Self) {
                 *self = *self + rhs;
             }
@@ -81,10 +95,13 @@ pub fn derive_add_assign(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_sub_assign(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::SubAssign for #name {
+        impl #impl_generics ::core::ops::SubAssign for #name #ty_generics #where_clause {
             fn sub_assign(&mut self, rhs: Self) {
                 *self = *self - rhs;
             }
@@ -93,10 +110,13 @@ pub fn derive_sub_assign(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_mul_assign(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::MulAssign for #name {
+        impl #impl_generics ::core::ops::MulAssign for #name #ty_generics #where_clause {
             fn mul_assign(&mut self, rhs: Self) {
                 *self = *self * rhs;
             }
@@ -105,10 +125,13 @@ pub fn derive_mul_assign(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_div_assign(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::DivAssign for #name {
+        impl #impl_generics ::core::ops::DivAssign for #name #ty_generics #where_clause {
             fn div_assign(&mut self, rhs: Self) {
                 *self = *self / rhs;
             }
@@ -117,10 +140,13 @@ pub fn derive_div_assign(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_rem_assign(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::RemAssign for #name {
+        impl #impl_generics ::core::ops::RemAssign for #name #ty_generics #where_clause {
             fn rem_assign(&mut self, rhs: Self) {
                 *self = *self % rhs;
             }
@@ -129,10 +155,13 @@ pub fn derive_rem_assign(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_eq(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::Eq for #name {}
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
     }
 }
 
@@ -142,11 +171,14 @@ pub fn derive_eq(ctx: &DeriveContextBase) -> TokenStream {
 // the operation on the base type.
 
 pub fn derive_add(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Add for #name {
+        impl #impl_generics ::core::ops::Add for #name #ty_generics #where_clause {
             type Output = Self;
             fn add(self, rhs: Self) -> Self::Output {
                 #self_as_idx::from_usize(
@@ -158,11 +190,14 @@ pub fn derive_add(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_sub(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Sub for #name {
+        impl #impl_generics ::core::ops::Sub for #name #ty_generics #where_clause {
             type Output = Self;
             fn sub(self, rhs: Self) -> Self::Output {
                 #self_as_idx::from_usize(
@@ -174,11 +209,14 @@ pub fn derive_sub(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_mul(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Mul for #name {
+        impl #impl_generics ::core::ops::Mul for #name #ty_generics #where_clause {
             type Output = Self;
             fn mul(self, rhs: Self) -> Self::Output {
                 #self_as_idx::from_usize(
@@ -190,11 +228,14 @@ pub fn derive_mul(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_div(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Div for #name {
+        impl #impl_generics ::core::ops::Div for #name #ty_generics #where_clause {
             type Output = Self;
             fn div(self, rhs: Self) -> Self::Output {
                 #self_as_idx::from_usize(
@@ -206,11 +247,14 @@ pub fn derive_div(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_rem(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Rem for #name {
+        impl #impl_generics ::core::ops::Rem for #name #ty_generics #where_clause {
             type Output = Self;
             fn rem(self, rhs: Self) -> Self::Output {
                 #self_as_idx::from_usize(
@@ -222,11 +266,14 @@ pub fn derive_rem(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_partial_ord(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::PartialOrd for #name {
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
             fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
                 #self_as_idx::into_usize(*self)
                     .partial_cmp(&#self_as_idx::into_usize(*other))
@@ -236,11 +283,14 @@ pub fn derive_partial_ord(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_ord(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::cmp::Ord for #name {
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
             fn cmp(&self, other: &Self) -> core::cmp::Ordering {
                 #self_as_idx::into_usize(*self)
                     .cmp(&#self_as_idx::into_usize(*other))
@@ -250,13 +300,16 @@ pub fn derive_ord(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_from_usize(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let self_as_idx = &ctx.self_as_idx;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::convert::From<usize> for #name {
+        impl #impl_generics ::core::convert::From<usize> for #name #ty_generics #where_clause {
             #[inline]
-            fn from(v: usize) -> #name {
+            fn from(v: usize) -> #name #ty_generics {
                 #self_as_idx::from_usize(v)
             }
         }
@@ -264,150 +317,183 @@ pub fn derive_from_usize(ctx: &DeriveContextBase) -> TokenStream {
 }
 
 pub fn derive_from_self_for_usize(ctx: &DeriveContextBase) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     // !! Can't use self_as_idx here because self is `usize`. !!
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::convert::From<#name> for usize {
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for usize #where_clause {
             #[inline]
-            fn from(v: #name) -> usize {
-                <#name as #indexland::Idx>::into_usize(v)
+            fn from(v: #name #ty_generics) -> usize {
+                <#name #ty_generics as #indexland::Idx>::into_usize(v)
             }
         }
     }
 }
 
 pub fn derive_add_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Add<#ty> for #name {
+        impl #impl_generics ::core::ops::Add<#ty> for #name #ty_generics #where_clause {
             type Output = Self;
             fn add(self, rhs: #ty) -> Self::Output {
-                self + #indexland::ArithCompat::<#name>::to_idx(rhs)
+                self + #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs)
             }
         }
     }
 }
 
 pub fn derive_sub_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Sub<#ty> for #name {
+        impl #impl_generics ::core::ops::Sub<#ty> for #name #ty_generics #where_clause {
             type Output = Self;
             fn sub(self, rhs: #ty) -> Self::Output {
-                self - #indexland::ArithCompat::<#name>::to_idx(rhs)
+                self - #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs)
             }
         }
     }
 }
 
 pub fn derive_mul_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Mul<#ty> for #name {
+        impl #impl_generics ::core::ops::Mul<#ty> for #name #ty_generics #where_clause {
             type Output = Self;
             fn mul(self, rhs: #ty) -> Self::Output {
-                self * #indexland::ArithCompat::<#name>::to_idx(rhs)
+                self * #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs)
             }
         }
     }
 }
 
 pub fn derive_div_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Div<#ty> for #name {
+        impl #impl_generics ::core::ops::Div<#ty> for #name #ty_generics #where_clause {
             type Output = Self;
             fn div(self, rhs: #ty) -> Self::Output {
-                self / #indexland::ArithCompat::<#name>::to_idx(rhs)
+                self / #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs)
             }
         }
     }
 }
 
 pub fn derive_rem_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::Rem<#ty> for #name {
+        impl #impl_generics ::core::ops::Rem<#ty> for #name #ty_generics #where_clause {
             type Output = Self;
             fn rem(self, rhs: #ty) -> Self::Output {
-                self % #indexland::ArithCompat::<#name>::to_idx(rhs)
+                self % #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs)
             }
         }
     }
 }
 
 pub fn derive_add_assign_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::AddAssign<#ty> for #name {
+        impl #impl_generics ::core::ops::AddAssign<#ty> for #name #ty_generics #where_clause {
             fn add_assign(&mut self, rhs: #ty) {
-                *self = *self + #indexland::ArithCompat::<#name>::to_idx(rhs);
+                *self = *self + #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs);
             }
         }
     }
 }
 
 pub fn derive_sub_assign_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::SubAssign<#ty> for #name {
+        impl #impl_generics ::core::ops::SubAssign<#ty> for #name #ty_generics #where_clause {
             fn sub_assign(&mut self, rhs: #ty) {
-                *self = *self - #indexland::ArithCompat::<#name>::to_idx(rhs);
+                *self = *self - #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs);
             }
         }
     }
 }
 
 pub fn derive_mul_assign_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::MulAssign<#ty> for #name {
+        impl #impl_generics ::core::ops::MulAssign<#ty> for #name #ty_generics #where_clause {
             fn mul_assign(&mut self, rhs: #ty) {
-                *self = *self * #indexland::ArithCompat::<#name>::to_idx(rhs);
+                *self = *self * #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs);
             }
         }
     }
 }
 
 pub fn derive_div_assign_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::DivAssign<#ty> for #name {
+        impl #impl_generics ::core::ops::DivAssign<#ty> for #name #ty_generics #where_clause {
             fn div_assign(&mut self, rhs: #ty) {
-                *self = *self / #indexland::ArithCompat::<#name>::to_idx(rhs);
+                *self = *self / #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs);
             }
         }
     }
 }
 
 pub fn derive_rem_assign_compat(ctx: &DeriveContextBase, ty: TokenStream) -> TokenStream {
+    let impl_generics = &ctx.impl_generics;
+    let ty_generics = &ctx.ty_generics;
+    let where_clause = &ctx.where_clause;
     let indexland = &ctx.attrs.indexland_path;
     let name = &ctx.name;
     quote! {
         #[automatically_derived]
-        impl ::core::ops::RemAssign<#ty> for #name {
+        impl #impl_generics ::core::ops::RemAssign<#ty> for #name #ty_generics #where_clause {
             fn rem_assign(&mut self, rhs: #ty) {
-                *self = *self % #indexland::ArithCompat::<#name>::to_idx(rhs);
+                *self = *self % #indexland::ArithCompat::<#name #ty_generics>::to_idx(rhs);
             }
         }
     }
@@ -1,6 +1,8 @@
 use std::ops::Add;
 
-use indexland::{EnumIndexArray, Idx, IdxNewtype, IndexArray, IndexArrayVec, index_array};
+use indexland::{
+    EnumIndexArray, Idx, IdxEnum, IdxNewtype, IndexArray, IndexArrayVec, IndexVec, index_array,
+};
 
 #[test]
 fn derive_enum_idx() {
@@ -226,6 +228,273 @@ pub fn wrapping_add_on_enum() {
     assert_eq!(E256::_200.wrapping_add(E256::_100), E256::_44);
 }
 
+#[test]
+fn derive_checked_idx() {
+    #[derive(Idx)]
+    struct NodeId(u16);
+
+    #[derive(Idx)]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    assert_eq!(NodeId::try_from_usize(70_000).unwrap_err().value(), 70_000);
+    assert_eq!(NodeId(1).checked_sub(NodeId(2)), None);
+    assert_eq!(NodeId(5).checked_offset(-2), Some(NodeId(3)));
+
+    assert_eq!(Side::try_from_usize(1), Ok(Side::Right));
+    let err = Side::try_from_usize(2).unwrap_err();
+    assert!(err.to_string().ends_with("Side"));
+    assert_eq!(Side::Left.checked_add(Side::Right), Some(Side::Right));
+    assert_eq!(Side::Right.checked_offset(1), None);
+    assert_eq!(Side::Right.distance(Side::Left), 1);
+}
+
+#[test]
+fn derive_delta() {
+    #[derive(Idx)]
+    #[indexland(delta = NodeDelta, bounds_checks = "always")]
+    struct NodeId(u8);
+
+    let mut id = NodeId(3);
+    let delta = NodeId(1) - id;
+    assert_eq!(delta, NodeDelta(-2));
+    assert_eq!(-delta + NodeDelta(1), NodeDelta(3));
+    assert_eq!(delta.to_string(), "-2");
+    id -= delta;
+    assert_eq!(id, NodeId(5));
+    assert!(std::panic::catch_unwind(|| NodeId(1) + delta).is_err());
+
+    let mut arr = IndexArray::<NodeId, i32, 4>::new([0, 1, 2, 3]);
+    assert_eq!(arr.get_relative(NodeId(3), delta), Some(&1));
+    assert_eq!(arr.get_relative(NodeId(1), delta), None);
+    arr.rotate_left_by(delta);
+    assert_eq!(arr, IndexArray::new([2, 3, 0, 1]));
+
+    #[derive(Idx)]
+    #[indexland(delta = WideDelta, bounds_checks = "always")]
+    struct WideId(usize);
+
+    // `isize::MIN` must not wrap around into an offset in the wrong direction
+    assert!(std::panic::catch_unwind(|| WideId(usize::MAX) - WideDelta(isize::MIN)).is_err());
+}
+
+#[test]
+fn derive_max() {
+    #[derive(Idx)]
+    #[indexland(max = 3, bounds_checks = "always")]
+    struct LaneId(u8);
+
+    assert_eq!(LaneId::MAX_USIZE, 3);
+    assert_eq!(LaneId(3).wrapping_add(LaneId(2)), LaneId(1));
+    assert_eq!(LaneId(2).saturating_add(LaneId(2)), LaneId::MAX);
+    assert!(std::panic::catch_unwind(|| LaneId::from_usize(4)).is_err());
+    assert!(std::panic::catch_unwind(|| LaneId(2) + LaneId(2)).is_err());
+    assert!(std::panic::catch_unwind(|| LaneId::from(10usize)).is_err());
+
+    #[derive(Idx)]
+    #[indexland(max = 10)]
+    struct Handle(std::num::NonZeroU32);
+
+    assert_eq!(Handle::MAX.into_usize(), 10);
+    assert_eq!(Handle::MAX.0.get(), 11);
+
+    let mut lanes = IndexVec::<LaneId, u8>::new();
+    for i in 0..4 {
+        lanes.push(i);
+    }
+    assert!(std::panic::catch_unwind(move || lanes.push_get_idx(4)).is_err());
+}
+
+#[test]
+fn derive_enum_discriminants() {
+    #[derive(Idx)]
+    #[indexland(extra(Repr))]
+    #[repr(u8)]
+    enum Opcode {
+        Halt = 10,
+        Jump = 3,
+        Load = 0x40,
+    }
+
+    assert_eq!(Opcode::Jump.into_usize(), 1);
+    assert_eq!(Opcode::from_usize(2), Opcode::Load);
+    assert_eq!(Opcode::MAX, Opcode::Load);
+    assert!(Opcode::Halt < Opcode::Jump);
+    assert_eq!(Opcode::Load.to_repr(), 0x40);
+    assert_eq!(Opcode::from_repr(3), Some(Opcode::Jump));
+    assert_eq!(Opcode::from_repr(1), None);
+
+    let table = IndexArray::<Opcode, &str, 3>::new(["halt", "jump", "load"]);
+    assert_eq!(table[Opcode::Load], "load");
+
+    #[derive(Idx)]
+    #[indexland(extra(Repr))]
+    enum Unit {
+        Only,
+    }
+
+    assert_eq!(Unit::ONE, Unit::Only);
+    assert_eq!(Unit::MAX_USIZE, 0);
+    assert_eq!(Unit::Only.wrapping_add(Unit::Only), Unit::Only);
+    assert_eq!(Unit::from_repr(0), Some(Unit::Only));
+}
+
+#[test]
+fn derive_variant_names() {
+    #[derive(Idx)]
+    #[indexland(rename_all = "SCREAMING-KEBAB-CASE", extra(FromStr))]
+    enum Phase {
+        LexTokens,
+        #[indexland(rename = "ir")]
+        LowerToIr,
+        Emit,
+    }
+
+    assert_eq!(Phase::VARIANT_NAMES, ["LEX-TOKENS", "ir", "EMIT"]);
+    assert_eq!(Phase::LowerToIr.name(), "ir");
+    assert_eq!(Phase::from_name("EMIT"), Some(Phase::Emit));
+    assert_eq!("LEX-TOKENS".parse::<Phase>(), Ok(Phase::LexTokens));
+    let err = "LexTokens".parse::<Phase>().unwrap_err();
+    assert!(err.to_string().ends_with("Phase"));
+
+    let times: EnumIndexArray<Phase, u32> = IndexArray::new([3, 1, 4]);
+    assert_eq!(
+        format!("{:?}", times.debug_map()),
+        "{LEX-TOKENS: 3, ir: 1, EMIT: 4}"
+    );
+    assert_eq!(format!("{times:?}"), "[3, 1, 4]");
+}
+
+#[test]
+fn derive_serde() {
+    #[derive(Idx)]
+    #[indexland(max = 9, extra(Serialize, Deserialize))]
+    struct SlotId(u8);
+
+    #[derive(Idx)]
+    #[indexland(rename_all = "lowercase", extra(Serialize, Deserialize))]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Idx)]
+    #[indexland(serde = "position", extra(Serialize, Deserialize))]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    let json = serde_json::to_string(&(SlotId(7), Color::Green, Side::Right)).unwrap();
+    assert_eq!(json, r#"[7,"green",1]"#);
+    let back: (SlotId, Color, Side) = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, (SlotId(7), Color::Green, Side::Right));
+
+    assert!(serde_json::from_str::<SlotId>("10").is_err());
+
+    #[derive(Idx)]
+    #[indexland(extra(Serialize, Deserialize))]
+    struct SignedId(i32);
+    assert!(serde_json::from_str::<SignedId>("-1").is_err());
+    assert_eq!(serde_json::from_str::<SignedId>("3").unwrap(), SignedId(3));
+    assert!(serde_json::from_str::<Color>(r#""Red""#).is_err());
+    assert!(serde_json::from_str::<Side>("2").is_err());
+}
+
+#[test]
+fn derive_generic_phantom_newtype() {
+    use std::marker::PhantomData;
+
+    #[derive(Idx)]
+    #[indexland(extra(Serialize, Deserialize))]
+    struct Id<T>(u32, PhantomData<fn() -> T>);
+
+    // neither `Copy` nor `Ord`
+    struct Node;
+
+    let mut nodes: IndexVec<Id<Node>, Node> = IndexVec::new();
+    let a = nodes.push_get_idx(Node);
+    let b = nodes.push_get_idx(Node);
+    assert_eq!(b - a, Id::ONE);
+    assert!(a < b);
+    assert_eq!(format!("{b:?}"), "1");
+    assert_eq!(serde_json::to_string(&b).unwrap(), "1");
+    assert_eq!(serde_json::from_str::<Id<Node>>("1").unwrap(), b);
+}
+
+#[test]
+fn derive_nonzero_newtype() {
+    use std::num::NonZeroU32;
+
+    #[derive(Idx)]
+    #[indexland(arith = "full")]
+    struct Handle(NonZeroU32);
+
+    assert_eq!(size_of::<Option<Handle>>(), size_of::<u32>());
+
+    let mut names: IndexVec<Handle, &str> = IndexVec::new();
+    let a = names.push_get_idx("a");
+    let b = names.push_get_idx("b");
+    assert_eq!(a.0.get(), 1);
+    assert_eq!(b, a + Handle::ONE);
+    assert_eq!(b * b - b, Handle::ZERO);
+    assert_eq!(format!("{b} {b:?}"), "1 1");
+    assert_eq!(names[b], "b");
+}
+
+#[test]
+fn derive_const_conversions() {
+    use std::num::NonZeroU16;
+
+    #[derive(Idx)]
+    #[indexland(extra(Const))]
+    struct NodeId(u32);
+
+    #[derive(Idx)]
+    #[indexland(max = 9, extra(Const))]
+    struct SlotId(u8);
+
+    #[derive(Idx)]
+    #[indexland(extra(Const))]
+    struct Handle(NonZeroU16);
+
+    #[derive(Idx)]
+    #[indexland(extra(Const))]
+    enum Flavor {
+        Sweet = 3,
+        Sour = 1,
+    }
+
+    const TABLE: IndexArray<NodeId, u32, 3> = index_array![NodeId;
+        NodeId(1) => 10,
+        NodeId(2) => 20,
+        NodeId(0) => 0,
+    ];
+    const X: u32 = *TABLE.index_const(NodeId::from_usize_const(2).into_usize_const());
+    assert_eq!(X, 20);
+    assert_eq!(TABLE.as_index_slice().get_const(3), None);
+
+    const SLOT: SlotId = SlotId::from_usize_const(9);
+    assert_eq!(SLOT, SlotId(9));
+    const HANDLE: Handle = Handle::from_usize_const(0);
+    assert_eq!(HANDLE.0.get(), 1);
+    const SOUR: usize = Flavor::Sour.into_usize_const();
+    assert_eq!(SOUR, 1);
+    assert_eq!(Flavor::from_usize_const(0), Flavor::Sweet);
+}
+
+#[test]
+#[should_panic(expected = "index out of range for SlotId")]
+fn derive_const_conversions_respect_max() {
+    #[derive(Idx)]
+    #[indexland(max = 9, extra(Const))]
+    struct SlotId(u8);
+
+    let _ = SlotId::from_usize_const(std::hint::black_box(10));
+}
+
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
@@ -0,0 +1,7 @@
+use indexland::{Idx, TypedId};
+
+#[derive(Idx)]
+#[indexland(extra(Const))]
+struct Id(TypedId<(), u32>);
+
+fn main() {}
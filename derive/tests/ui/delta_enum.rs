@@ -0,0 +1,10 @@
+use indexland::Idx;
+
+#[derive(Idx)]
+#[indexland(delta = FooDelta)]
+enum Foo {
+    A,
+    B,
+}
+
+fn main() {}
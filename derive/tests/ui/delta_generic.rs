@@ -0,0 +1,9 @@
+use std::marker::PhantomData;
+
+use indexland::Idx;
+
+#[derive(Idx)]
+#[indexland(delta = IdDelta)]
+struct Id<T>(u32, PhantomData<fn() -> T>);
+
+fn main() {}
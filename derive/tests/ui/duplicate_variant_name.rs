@@ -0,0 +1,11 @@
+use indexland::Idx;
+
+#[derive(Idx)]
+#[indexland(rename_all = "lowercase")]
+enum Foo {
+    A,
+    #[indexland(rename = "a")]
+    B,
+}
+
+fn main() {}
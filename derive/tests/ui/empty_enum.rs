@@ -0,0 +1,6 @@
+use indexland::Idx;
+
+#[derive(Idx)]
+enum Never {}
+
+fn main() {}
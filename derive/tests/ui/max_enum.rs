@@ -0,0 +1,10 @@
+use indexland::Idx;
+
+#[derive(Idx)]
+#[indexland(max = 1)]
+enum Foo {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use indexland::{Idx, TypedId};
+
+#[derive(Idx)]
+#[indexland(max = 10)]
+struct Id(TypedId<(), u32>);
+
+fn main() {}
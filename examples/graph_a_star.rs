@@ -8,17 +8,22 @@
 //! which is typically considered challenging without libraries
 //! due the borrow checker preventing cyclic references.
 //!
+//! The search itself is done by [`algo::astar`], which works on any graph
+//! implementing the small [`algo::WeightedAdjacency`] trait.
+//!
 //! The implementation details are mostly irrelevant, this is just meant to
 //! show a few indexland features, each highlighted with `// NOTE`.
 
-use indexland::{Idx, IndexSlice, IndexVec, NonMax, index_vec};
-use std::{cmp::Ordering, collections::BinaryHeap};
+use indexland::{
+    Idx, IndexVec, NonMax,
+    algo::{self, Adjacency, WeightedAdjacency},
+};
 
 #[derive(Idx)]
 pub struct NodeId(u32);
 
 // NOTE: Using [`NonMax<u32>`] ensures that [`Option<EdgeId>`] is
-// 4 bytes instead of 8, which saves some space in the A* `came_from` table.
+// 4 bytes instead of 8, in case edge ids ever need to be optional.
 #[derive(Idx)]
 pub struct EdgeId(NonMax<u32>);
 
@@ -49,38 +54,10 @@ pub struct Node {
 
 #[derive(Clone, Debug)]
 pub struct Edge {
-    from: NodeId,
     to: NodeId,
     cost: i32,
 }
 
-// A* Node metadata
-#[derive(PartialEq, Eq)]
-struct NodeInfo {
-    node: NodeId,
-    cost: i32,
-    distance_to_goal_estimate: i32,
-}
-
-// Custom ordering for our priority queue
-impl Ord for NodeInfo {
-    fn cmp(&self, other: &Self) -> Ordering {
-        (other.cost + other.distance_to_goal_estimate)
-            .cmp(&(self.cost + self.distance_to_goal_estimate))
-            .then_with(|| {
-                other
-                    .distance_to_goal_estimate
-                    .cmp(&self.distance_to_goal_estimate)
-            })
-    }
-}
-
-impl PartialOrd for NodeInfo {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 impl Graph {
     pub const fn new() -> Self {
         Self {
@@ -107,14 +84,10 @@ impl Graph {
     // the wrong type here which can lead to very helful error messages
     // e.g. when when refactoring parameter order.
     pub fn add_bidi_edge(&mut self, from: NodeId, to: NodeId, cost: i32) {
-        let edge_id_fwd = self.edges.push_get_idx(Edge { from, to, cost });
+        let edge_id_fwd = self.edges.push_get_idx(Edge { to, cost });
         self.nodes[from].edges.push(edge_id_fwd);
 
-        let edge_id_bwd = self.edges.push_get_idx(Edge {
-            from: to,
-            to: from,
-            cost,
-        });
+        let edge_id_bwd = self.edges.push_get_idx(Edge { to: from, cost });
         self.nodes[to].edges.push(edge_id_bwd);
     }
 
@@ -125,72 +98,62 @@ impl Graph {
         (((to.x - from.x).pow(2) + (to.y - from.y).pow(2)) as f32).sqrt() as i32
     }
 
-    pub fn find_path(&self, start: NodeId, goal: NodeId) -> Option<Vec<(NodeId, EdgeId)>> {
-        let mut open_set = BinaryHeap::new();
-        // NOTE: again, `IndexVec`s make this code much more self explanatory
-        let mut came_from: IndexVec<NodeId, Option<EdgeId>> = index_vec![];
-        let mut g_score: IndexVec<NodeId, i32> = index_vec![];
+    pub fn find_path(&self, start: NodeId, goal: NodeId) -> Option<(i32, Vec<NodeId>)> {
+        algo::astar(self, start, goal, |node| self.heuristic(node, goal))
+    }
 
-        // NOTE: `indices` is another convenience helper for iterating over the
-        // typed indices of a container.
-        // (In this case `0..nodes.len()` would have worked aswell).
-        for _ in self.nodes.indices() {
-            came_from.push(None);
-            // Initialize best found route score with "infinity"
-            g_score.push(i32::MAX);
-        }
+    fn heuristic(&self, from: NodeId, to: NodeId) -> i32 {
+        self.calculate_distance(from, to)
+    }
 
-        g_score[start] = 0;
-        open_set.push(NodeInfo {
-            node: start,
-            cost: 0,
-            distance_to_goal_estimate: self.heuristic(start, goal),
-        });
+    fn edge_cost(&self, from: NodeId, to: NodeId) -> i32 {
+        self.weighted_neighbors(from)
+            .filter(|&(node, _)| node == to)
+            .map(|(_, cost)| cost)
+            .min()
+            .unwrap()
+    }
+}
 
-        while let Some(current) = open_set.pop() {
-            if current.node == goal {
-                return Some(self.reconstruct_path(&came_from, goal));
-            }
+/// Iterator over the outgoing edges of a node, see [`WeightedAdjacency`].
+pub struct Neighbors<'a> {
+    graph: &'a Graph,
+    edges: std::slice::Iter<'a, EdgeId>,
+}
 
-            for &edge_id in &self.nodes[current.node].edges {
-                let edge = &self.edges[edge_id];
-                let neighbor = edge.to;
-                let tentative_g_score = g_score[current.node] + edge.cost;
+impl Iterator for Neighbors<'_> {
+    type Item = (NodeId, i32);
 
-                if tentative_g_score < g_score[neighbor] {
-                    came_from[neighbor] = Some(edge_id);
-                    g_score[neighbor] = tentative_g_score;
-                    open_set.push(NodeInfo {
-                        node: neighbor,
-                        cost: tentative_g_score,
-                        distance_to_goal_estimate: self.heuristic(neighbor, goal),
-                    });
-                }
-            }
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        // NOTE: the typed ids make it obvious which table each lookup goes to.
+        let edge = &self.graph.edges[*self.edges.next()?];
+        Some((edge.to, edge.cost))
+    }
+}
 
-        None
+// NOTE: implementing the adjacency traits for our own graph type gives us
+// all of the algorithms in `indexland::algo` for free.
+impl Adjacency<NodeId> for Graph {
+    type Neighbors<'a> = std::iter::Map<Neighbors<'a>, fn((NodeId, i32)) -> NodeId>;
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
     }
 
-    fn heuristic(&self, from: NodeId, to: NodeId) -> i32 {
-        self.calculate_distance(from, to)
+    fn neighbors(&self, node: NodeId) -> Self::Neighbors<'_> {
+        self.weighted_neighbors(node).map(|(node, _)| node)
     }
+}
 
-    fn reconstruct_path(
-        &self,
-        came_from: &IndexSlice<NodeId, Option<EdgeId>>,
-        goal: NodeId,
-    ) -> Vec<(NodeId, EdgeId)> {
-        let mut path = Vec::new();
-        let mut current = goal;
+impl WeightedAdjacency<NodeId> for Graph {
+    type Weight = i32;
+    type WeightedNeighbors<'a> = Neighbors<'a>;
 
-        while let Some(edge) = came_from[current] {
-            path.push((current, edge));
-            current = self.edges[edge].from;
+    fn weighted_neighbors(&self, node: NodeId) -> Neighbors<'_> {
+        Neighbors {
+            graph: self,
+            edges: self.nodes[node].edges.iter(),
         }
-
-        path.reverse();
-        path
     }
 }
 
@@ -235,22 +198,19 @@ fn main() -> Result<(), i8> {
     graph.add_bidi_edge(e, f, 3);
     graph.add_bidi_edge(e, g, 1);
 
-    let Some(path) = graph.find_path(s, g) else {
+    let Some((_, path)) = graph.find_path(s, g) else {
         println!("No path found!");
         return Err(1);
     };
 
     println!("Path found! Node sequence:");
 
-    let start = &graph.nodes[s];
-    println!(
-        "Node {} at position: ({}, {}) [total cost 0]",
-        start.name, start.pos.x, start.pos.y
-    );
     let mut total_cost = 0;
-    for (node, edge) in path {
-        total_cost += graph.edges[edge].cost;
-        let node = &graph.nodes[node];
+    for (i, &id) in path.iter().enumerate() {
+        if i > 0 {
+            total_cost += graph.edge_cost(path[i - 1], id);
+        }
+        let node = &graph.nodes[id];
         println!(
             "Node {} at position: ({}, {}) [total cost {}]",
             node.name, node.pos.x, node.pos.y, total_cost
@@ -0,0 +1,817 @@
+//! Graph algorithms over typed node ids.
+//!
+//! All algorithms operate on graphs implementing the small [`Adjacency`]
+//! trait, which is implemented for adjacency lists like
+//! `IndexSlice<N, Vec<N>>` as well as for [`IndexGraph`]. Shortest path
+//! algorithms additionally require [`WeightedAdjacency`], which is
+//! implemented for `IndexSlice<N, Vec<(N, W)>>` and for [`IndexGraph`]s with
+//! `Copy` edge weights.
+//!
+//! Results are returned as [`IndexVec`]s keyed by the node type of the graph.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexVec, algo, index_vec};
+//!
+//! #[derive(Idx)]
+//! struct BlockId(u32);
+//!
+//! let cfg: IndexVec<BlockId, Vec<BlockId>> = index_vec![
+//!     vec![BlockId(1), BlockId(2)],
+//!     vec![BlockId(3)],
+//!     vec![BlockId(3)],
+//!     vec![],
+//! ];
+//!
+//! let order = algo::toposort(&cfg).unwrap();
+//! assert_eq!(order.first(), Some(&BlockId(0)));
+//! assert_eq!(order.last(), Some(&BlockId(3)));
+//!
+//! let doms = algo::dominators(&cfg, BlockId(0));
+//! assert_eq!(doms.immediate_dominator(BlockId(3)), Some(BlockId(0)));
+//! ```
+
+use core::{cmp::Reverse, ops::Add};
+
+use alloc::{
+    collections::{BinaryHeap, VecDeque},
+    vec,
+    vec::Vec,
+};
+
+use crate::{Idx, IndexGraph, IndexSlice, IndexVec, index_graph::Adjacent};
+
+/// A directed graph whose nodes are the indices `0..node_count()` of type `N`.
+pub trait Adjacency<N> {
+    type Neighbors<'a>: Iterator<Item = N>
+    where
+        Self: 'a;
+
+    fn node_count(&self) -> usize;
+
+    /// The successors of `node`.
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_>;
+}
+
+/// An [`Adjacency`] whose edges carry a weight.
+pub trait WeightedAdjacency<N>: Adjacency<N> {
+    type Weight;
+
+    type WeightedNeighbors<'a>: Iterator<Item = (N, Self::Weight)>
+    where
+        Self: 'a;
+
+    /// The successors of `node` together with the weights of the edges
+    /// leading to them.
+    fn weighted_neighbors(&self, node: N) -> Self::WeightedNeighbors<'_>;
+}
+
+impl<N: Idx> Adjacency<N> for IndexSlice<N, Vec<N>> {
+    type Neighbors<'a> = core::iter::Copied<core::slice::Iter<'a, N>>;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        self[node].iter().copied()
+    }
+}
+
+impl<N: Idx> Adjacency<N> for IndexVec<N, Vec<N>> {
+    type Neighbors<'a> = core::iter::Copied<core::slice::Iter<'a, N>>;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        self[node].iter().copied()
+    }
+}
+
+fn weighted_target<N: Copy, W>(&(node, _): &(N, W)) -> N {
+    node
+}
+
+fn weighted_edge<N: Copy, W: Copy>(&edge: &(N, W)) -> (N, W) {
+    edge
+}
+
+impl<N: Idx, W: Copy> Adjacency<N> for IndexSlice<N, Vec<(N, W)>> {
+    type Neighbors<'a>
+        = core::iter::Map<core::slice::Iter<'a, (N, W)>, fn(&(N, W)) -> N>
+    where
+        W: 'a;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        self[node].iter().map(weighted_target as fn(&_) -> _)
+    }
+}
+
+impl<N: Idx, W: Copy> WeightedAdjacency<N> for IndexSlice<N, Vec<(N, W)>> {
+    type Weight = W;
+
+    type WeightedNeighbors<'a>
+        = core::iter::Map<core::slice::Iter<'a, (N, W)>, fn(&(N, W)) -> (N, W)>
+    where
+        W: 'a;
+
+    fn weighted_neighbors(&self, node: N) -> Self::WeightedNeighbors<'_> {
+        self[node].iter().map(weighted_edge as fn(&_) -> _)
+    }
+}
+
+impl<N: Idx, W: Copy> Adjacency<N> for IndexVec<N, Vec<(N, W)>> {
+    type Neighbors<'a>
+        = core::iter::Map<core::slice::Iter<'a, (N, W)>, fn(&(N, W)) -> N>
+    where
+        W: 'a;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        self.as_index_slice().neighbors(node)
+    }
+}
+
+impl<N: Idx, W: Copy> WeightedAdjacency<N> for IndexVec<N, Vec<(N, W)>> {
+    type Weight = W;
+
+    type WeightedNeighbors<'a>
+        = core::iter::Map<core::slice::Iter<'a, (N, W)>, fn(&(N, W)) -> (N, W)>
+    where
+        W: 'a;
+
+    fn weighted_neighbors(&self, node: N) -> Self::WeightedNeighbors<'_> {
+        self.as_index_slice().weighted_neighbors(node)
+    }
+}
+
+/// Successors of a node in an [`IndexGraph`].
+pub struct GraphNeighbors<'a, N, E> {
+    adjacent: Adjacent<'a, N, E>,
+}
+
+impl<N: Idx, E: Idx> Iterator for GraphNeighbors<'_, N, E> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        self.adjacent.next().map(|(_, node)| node)
+    }
+}
+
+/// Successors of a node in an [`IndexGraph`] together with the edge weights.
+pub struct GraphWeightedNeighbors<'a, N, E, EW> {
+    adjacent: Adjacent<'a, N, E>,
+    weights: &'a IndexSlice<E, EW>,
+}
+
+impl<N: Idx, E: Idx, EW: Copy> Iterator for GraphWeightedNeighbors<'_, N, E, EW> {
+    type Item = (N, EW);
+
+    fn next(&mut self) -> Option<(N, EW)> {
+        let (edge, node) = self.adjacent.next()?;
+        Some((node, self.weights[edge]))
+    }
+}
+
+impl<N: Idx, E: Idx, NW, EW> Adjacency<N> for IndexGraph<N, E, NW, EW> {
+    type Neighbors<'a>
+        = GraphNeighbors<'a, N, E>
+    where
+        Self: 'a;
+
+    fn node_count(&self) -> usize {
+        self.node_count()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        GraphNeighbors {
+            adjacent: self.outgoing(node),
+        }
+    }
+}
+
+impl<N: Idx, E: Idx, NW, EW: Copy> WeightedAdjacency<N> for IndexGraph<N, E, NW, EW> {
+    type Weight = EW;
+
+    type WeightedNeighbors<'a>
+        = GraphWeightedNeighbors<'a, N, E, EW>
+    where
+        Self: 'a;
+
+    fn weighted_neighbors(&self, node: N) -> Self::WeightedNeighbors<'_> {
+        GraphWeightedNeighbors {
+            adjacent: self.outgoing(node),
+            weights: self.edge_weights(),
+        }
+    }
+}
+
+fn node_table<N, T: Clone>(graph: &(impl Adjacency<N> + ?Sized), value: T) -> IndexVec<N, T> {
+    IndexVec::from(vec![value; graph.node_count()])
+}
+
+// ===== Bfs =====
+
+/// Breadth first traversal starting at a root node, see [`bfs`].
+pub struct Bfs<'a, G: ?Sized, N> {
+    graph: &'a G,
+    queue: VecDeque<N>,
+    discovered: IndexVec<N, bool>,
+}
+
+/// Visits all nodes reachable from `root` in breadth first order.
+pub fn bfs<G, N>(graph: &G, root: N) -> Bfs<'_, G, N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    let mut discovered = node_table(graph, false);
+    discovered[root] = true;
+    Bfs {
+        graph,
+        queue: VecDeque::from([root]),
+        discovered,
+    }
+}
+
+impl<G, N> Iterator for Bfs<'_, G, N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let node = self.queue.pop_front()?;
+        for succ in self.graph.neighbors(node) {
+            if !core::mem::replace(&mut self.discovered[succ], true) {
+                self.queue.push_back(succ);
+            }
+        }
+        Some(node)
+    }
+}
+
+// ===== Dfs =====
+
+/// Depth first traversal in preorder starting at a root node, see [`dfs`].
+pub struct Dfs<'a, G: ?Sized, N> {
+    graph: &'a G,
+    stack: Vec<N>,
+    visited: IndexVec<N, bool>,
+}
+
+/// Visits all nodes reachable from `root` in depth first preorder.
+pub fn dfs<G, N>(graph: &G, root: N) -> Dfs<'_, G, N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    Dfs {
+        graph,
+        stack: vec![root],
+        visited: node_table(graph, false),
+    }
+}
+
+impl<G, N> Iterator for Dfs<'_, G, N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        while let Some(node) = self.stack.pop() {
+            if core::mem::replace(&mut self.visited[node], true) {
+                continue;
+            }
+            let start = self.stack.len();
+            self.stack.extend(self.graph.neighbors(node));
+            // visit successors in their original order
+            self.stack[start..].reverse();
+            return Some(node);
+        }
+        None
+    }
+}
+
+// ===== Postorder =====
+
+/// Visits every node reachable from `roots` once in depth first postorder,
+/// calling `on_back_edge` with the current stack when a back edge to the
+/// node at the given stack depth is found.
+fn postorder<G, N>(
+    graph: &G,
+    roots: impl IntoIterator<Item = N>,
+    mut on_back_edge: impl FnMut(&[(N, G::Neighbors<'_>)], usize) -> bool,
+) -> Vec<N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    const UNVISITED: usize = usize::MAX;
+    const DONE: usize = usize::MAX - 1;
+
+    // depth on the stack while a node is active
+    let mut state = node_table(graph, UNVISITED);
+    let mut order = Vec::with_capacity(graph.node_count());
+    let mut stack = Vec::new();
+
+    for root in roots {
+        if state[root] != UNVISITED {
+            continue;
+        }
+        state[root] = 0;
+        stack.push((root, graph.neighbors(root)));
+        while let Some((node, successors)) = stack.last_mut() {
+            let node = *node;
+            let Some(succ) = successors.next() else {
+                state[node] = DONE;
+                order.push(node);
+                let _ = stack.pop();
+                continue;
+            };
+            match state[succ] {
+                UNVISITED => {
+                    state[succ] = stack.len();
+                    stack.push((succ, graph.neighbors(succ)));
+                }
+                DONE => (),
+                depth => {
+                    if on_back_edge(&stack, depth) {
+                        return order;
+                    }
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Returns the nodes reachable from `root` in reverse postorder.
+pub fn reverse_postorder<G, N>(graph: &G, root: N) -> Vec<N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    let mut order = postorder(graph, [root], |_, _| false);
+    order.reverse();
+    order
+}
+
+/// Numbers the nodes reachable from `root` in reverse postorder.
+/// Unreachable nodes are `None`.
+pub fn reverse_postorder_numbering<G, N>(graph: &G, root: N) -> IndexVec<N, Option<usize>>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    let mut numbering = node_table(graph, None);
+    for (i, node) in reverse_postorder(graph, root).into_iter().enumerate() {
+        numbering[node] = Some(i);
+    }
+    numbering
+}
+
+// ===== Toposort =====
+
+/// A cycle found by [`toposort`]. Every node in [`nodes`](Self::nodes) has
+/// an edge to the next one, and the last one has an edge to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    pub nodes: Vec<N>,
+}
+
+/// Orders all nodes so that every edge points from an earlier to a later
+/// node, or returns a cycle if no such order exists.
+pub fn toposort<G, N>(graph: &G) -> Result<Vec<N>, Cycle<N>>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    let mut cycle = None;
+    let mut order = postorder(
+        graph,
+        (0..graph.node_count()).map(N::from_usize),
+        |stack, depth| {
+            cycle = Some(Cycle {
+                nodes: stack[depth..].iter().map(|(node, _)| *node).collect(),
+            });
+            true
+        },
+    );
+    if let Some(cycle) = cycle {
+        return Err(cycle);
+    }
+    order.reverse();
+    Ok(order)
+}
+
+// ===== Tarjan SCC =====
+
+/// Computes the strongly connected components of the graph using Tarjan's
+/// algorithm.
+///
+/// Returns the component of every node. Components are numbered in reverse
+/// topological order of the condensed graph, so edges between different
+/// components always point to a component with a smaller id.
+pub fn tarjan_scc<G, N, S>(graph: &G) -> IndexVec<N, S>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+    S: Idx,
+{
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = node_table(graph, UNVISITED);
+    let mut lowlink = node_table(graph, 0);
+    let mut component = node_table(graph, UNVISITED);
+    let mut component_count = 0;
+    let mut next_index = 0;
+    let mut scc_stack = Vec::new();
+    let mut call_stack = Vec::new();
+
+    for root in (0..graph.node_count()).map(N::from_usize) {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        scc_stack.push(root);
+        call_stack.push((root, graph.neighbors(root)));
+
+        while let Some((node, successors)) = call_stack.last_mut() {
+            let node = *node;
+            if let Some(succ) = successors.next() {
+                if index[succ] == UNVISITED {
+                    index[succ] = next_index;
+                    lowlink[succ] = next_index;
+                    next_index += 1;
+                    scc_stack.push(succ);
+                    call_stack.push((succ, graph.neighbors(succ)));
+                } else if component[succ] == UNVISITED {
+                    // still on the scc stack
+                    lowlink[node] = lowlink[node].min(index[succ]);
+                }
+                continue;
+            }
+            let _ = call_stack.pop();
+            if let Some((parent, _)) = call_stack.last() {
+                lowlink[*parent] = lowlink[*parent].min(lowlink[node]);
+            }
+            if lowlink[node] == index[node] {
+                while let Some(member) = scc_stack.pop() {
+                    component[member] = component_count;
+                    if member.into_usize() == node.into_usize() {
+                        break;
+                    }
+                }
+                component_count += 1;
+            }
+        }
+    }
+
+    IndexVec::from(component.into_iter().map(S::from_usize).collect::<Vec<_>>())
+}
+
+// ===== Dominators =====
+
+/// The dominator tree of a graph, see [`dominators`].
+#[derive(Debug, Clone)]
+pub struct Dominators<N> {
+    root: N,
+    idom: IndexVec<N, Option<N>>,
+}
+
+impl<N: Idx> Dominators<N> {
+    pub fn root(&self) -> N {
+        self.root
+    }
+
+    /// The immediate dominator of `node`. `None` for the root and for
+    /// unreachable nodes.
+    pub fn immediate_dominator(&self, node: N) -> Option<N> {
+        if node.into_usize() == self.root.into_usize() {
+            return None;
+        }
+        self.idom[node]
+    }
+
+    /// Iterates over all dominators of `node`, starting with `node` itself
+    /// and ending with the root. Empty for unreachable nodes.
+    pub fn dominators(&self, node: N) -> impl Iterator<Item = N> + '_ {
+        let start = self.idom[node].map(|_| node);
+        core::iter::successors(start, |&n| self.immediate_dominator(n))
+    }
+
+    /// Returns `true` if every path from the root to `b` passes through `a`.
+    pub fn dominates(&self, a: N, b: N) -> bool {
+        self.dominators(b).any(|n| n.into_usize() == a.into_usize())
+    }
+
+    /// The immediate dominators of all nodes. The root is its own immediate
+    /// dominator, unreachable nodes are `None`.
+    pub fn as_index_slice(&self) -> &IndexSlice<N, Option<N>> {
+        &self.idom
+    }
+}
+
+/// Computes the dominator tree of all nodes reachable from `root`, using
+/// the algorithm by Cooper, Harvey and Kennedy.
+pub fn dominators<G, N>(graph: &G, root: N) -> Dominators<N>
+where
+    G: Adjacency<N> + ?Sized,
+    N: Idx,
+{
+    let order = reverse_postorder(graph, root);
+    let mut rpo_number = node_table(graph, usize::MAX);
+    for (i, &node) in order.iter().enumerate() {
+        rpo_number[node] = i;
+    }
+    // predecessors by rpo number, restricted to reachable nodes
+    let mut preds = vec![Vec::new(); order.len()];
+    for (i, &node) in order.iter().enumerate() {
+        for succ in graph.neighbors(node) {
+            preds[rpo_number[succ]].push(i);
+        }
+    }
+
+    // immediate dominators by rpo number
+    let mut idom = vec![usize::MAX; order.len()];
+    idom[0] = 0;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for b in 1..order.len() {
+            let mut new_idom = usize::MAX;
+            for &p in &preds[b] {
+                if idom[p] == usize::MAX {
+                    continue;
+                }
+                if new_idom == usize::MAX {
+                    new_idom = p;
+                    continue;
+                }
+                let (mut x, mut y) = (p, new_idom);
+                while x != y {
+                    while x > y {
+                        x = idom[x];
+                    }
+                    while y > x {
+                        y = idom[y];
+                    }
+                }
+                new_idom = x;
+            }
+            if idom[b] != new_idom {
+                idom[b] = new_idom;
+                changed = true;
+            }
+        }
+    }
+
+    let mut res = node_table(graph, None);
+    for (i, &node) in order.iter().enumerate() {
+        res[node] = Some(order[idom[i]]);
+    }
+    Dominators { root, idom: res }
+}
+
+// ===== Shortest Paths =====
+
+/// The result of [`dijkstra`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, W> {
+    /// The distance from the start node, `None` for unreachable nodes.
+    pub distance: IndexVec<N, Option<W>>,
+    /// The previous node on a shortest path from the start node.
+    pub predecessor: IndexVec<N, Option<N>>,
+}
+
+impl<N: Idx, W> ShortestPaths<N, W> {
+    /// Returns a shortest path from the start node to `target`,
+    /// including both ends.
+    pub fn path_to(&self, target: N) -> Option<Vec<N>> {
+        let _ = self.distance[target].as_ref()?;
+        let mut path: Vec<N> =
+            core::iter::successors(Some(target), |&n| self.predecessor[n]).collect();
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Computes the shortest paths from `start` to all reachable nodes.
+/// Edge weights must not be negative.
+pub fn dijkstra<G, N>(graph: &G, start: N) -> ShortestPaths<N, G::Weight>
+where
+    G: WeightedAdjacency<N> + ?Sized,
+    N: Idx,
+    G::Weight: Copy + Ord + Default + Add<Output = G::Weight>,
+{
+    let mut distance = node_table(graph, None);
+    let mut predecessor = node_table(graph, None);
+    let mut heap = BinaryHeap::new();
+    distance[start] = Some(G::Weight::default());
+    heap.push(Reverse((G::Weight::default(), start.into_usize())));
+
+    while let Some(Reverse((dist, node))) = heap.pop() {
+        let node = N::from_usize(node);
+        if distance[node].is_some_and(|d| d < dist) {
+            continue;
+        }
+        for (succ, weight) in graph.weighted_neighbors(node) {
+            let new_dist = dist + weight;
+            if distance[succ].is_none_or(|d| new_dist < d) {
+                distance[succ] = Some(new_dist);
+                predecessor[succ] = Some(node);
+                heap.push(Reverse((new_dist, succ.into_usize())));
+            }
+        }
+    }
+    ShortestPaths {
+        distance,
+        predecessor,
+    }
+}
+
+/// Finds a shortest path from `start` to `goal` using the A* algorithm.
+///
+/// `heuristic` estimates the remaining cost to reach `goal` and must never
+/// overestimate it. Returns the cost of the path and the path itself,
+/// including both ends.
+pub fn astar<G, N>(
+    graph: &G,
+    start: N,
+    goal: N,
+    mut heuristic: impl FnMut(N) -> G::Weight,
+) -> Option<(G::Weight, Vec<N>)>
+where
+    G: WeightedAdjacency<N> + ?Sized,
+    N: Idx,
+    G::Weight: Copy + Ord + Default + Add<Output = G::Weight>,
+{
+    let mut cost = node_table(graph, None);
+    let mut predecessor = node_table(graph, None::<N>);
+    let mut heap = BinaryHeap::new();
+    cost[start] = Some(G::Weight::default());
+    heap.push(Reverse((
+        heuristic(start),
+        G::Weight::default(),
+        start.into_usize(),
+    )));
+
+    while let Some(Reverse((_, node_cost, node))) = heap.pop() {
+        let node = N::from_usize(node);
+        if cost[node].is_some_and(|c| c < node_cost) {
+            continue;
+        }
+        if node.into_usize() == goal.into_usize() {
+            let mut path: Vec<N> =
+                core::iter::successors(Some(goal), |&n| predecessor[n]).collect();
+            path.reverse();
+            return Some((node_cost, path));
+        }
+        for (succ, weight) in graph.weighted_neighbors(node) {
+            let new_cost = node_cost + weight;
+            if cost[succ].is_none_or(|c| new_cost < c) {
+                cost[succ] = Some(new_cost);
+                predecessor[succ] = Some(node);
+                heap.push(Reverse((
+                    new_cost + heuristic(succ),
+                    new_cost,
+                    succ.into_usize(),
+                )));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+    use core::cell::Cell;
+
+    use crate::{IndexGraph, IndexVec, index_vec};
+
+    use super::{
+        Adjacency, Cycle, WeightedAdjacency, astar, bfs, dfs, dijkstra, dominators, tarjan_scc,
+        toposort,
+    };
+
+    fn diamond() -> IndexVec<usize, Vec<usize>> {
+        // 0 -> 1 -> 3 -> 4
+        //  \-> 2 -/
+        index_vec![vec![1, 2], vec![3], vec![3], vec![4], vec![]]
+    }
+
+    #[test]
+    fn traversal() {
+        let g = diamond();
+        assert!(bfs(&g, 0).eq([0, 1, 2, 3, 4]));
+        assert!(dfs(&g, 0).eq([0, 1, 3, 4, 2]));
+        assert_eq!(toposort(&g), Ok(vec![0, 2, 1, 3, 4]));
+
+        let cyclic: IndexVec<usize, Vec<usize>> = index_vec![vec![1], vec![2], vec![1]];
+        assert_eq!(toposort(&cyclic), Err(Cycle { nodes: vec![1, 2] }));
+    }
+
+    #[test]
+    fn scc_and_dominators() {
+        let g: IndexVec<usize, Vec<usize>> =
+            index_vec![vec![1], vec![2], vec![1, 3], vec![4], vec![3], vec![]];
+        let scc = tarjan_scc::<_, _, u32>(&g);
+        assert_eq!(scc.as_slice(), [2, 1, 1, 0, 0, 3]);
+
+        let doms = dominators(&diamond(), 0);
+        assert_eq!(doms.immediate_dominator(3), Some(0));
+        assert_eq!(doms.immediate_dominator(4), Some(3));
+        assert_eq!(doms.immediate_dominator(0), None);
+        assert!(doms.dominates(3, 4));
+        assert!(!doms.dominates(1, 4));
+        assert!(doms.dominators(4).eq([4, 3, 0]));
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let g: IndexVec<usize, Vec<(usize, u32)>> = index_vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![],
+            vec![],
+        ];
+        let paths = dijkstra(&g, 0);
+        assert_eq!(
+            paths.distance.as_slice(),
+            [Some(0), Some(3), Some(1), Some(4), None]
+        );
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.path_to(4), None);
+        assert_eq!(astar(&g, 0, 3, |_| 0), Some((4, vec![0, 2, 1, 3])));
+        assert_eq!(astar(&g, 0, 4, |_| 0), None);
+
+        let mut graph = IndexGraph::<u32, u32, i32, u32>::new_undirected();
+        let [a, b, c] = [0, 5, 10].map(|x| graph.add_node(x));
+        let _ = graph.add_edge(a, b, 5);
+        let _ = graph.add_edge(c, b, 5);
+        let _ = graph.add_edge(a, c, 20);
+        let target = graph.node_weights()[c];
+        let estimate = |n| target.abs_diff(graph.node_weights()[n]);
+        assert_eq!(astar(&graph, a, c, estimate), Some((10, vec![a, b, c])));
+    }
+
+    #[test]
+    fn astar_skips_stale_entries() {
+        struct Counting {
+            graph: IndexVec<usize, Vec<(usize, u32)>>,
+            expanded: Cell<usize>,
+        }
+        impl Adjacency<usize> for Counting {
+            type Neighbors<'a> =
+                <IndexVec<usize, Vec<(usize, u32)>> as Adjacency<usize>>::Neighbors<'a>;
+            fn node_count(&self) -> usize {
+                self.graph.len()
+            }
+            fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+                self.graph.neighbors(node)
+            }
+        }
+        impl WeightedAdjacency<usize> for Counting {
+            type Weight = u32;
+            type WeightedNeighbors<'a> =
+                <IndexVec<usize, Vec<(usize, u32)>> as WeightedAdjacency<usize>>::WeightedNeighbors<
+                    'a,
+                >;
+            fn weighted_neighbors(&self, node: usize) -> Self::WeightedNeighbors<'_> {
+                self.expanded.set(self.expanded.get() + 1);
+                self.graph.weighted_neighbors(node)
+            }
+        }
+
+        // node 1 is first reached at cost 10, then at cost 2 through node 2
+        let g = Counting {
+            graph: index_vec![
+                vec![(1, 10), (2, 1)],
+                vec![(3, 1)],
+                vec![(1, 1)],
+                vec![],
+                vec![]
+            ],
+            expanded: Cell::new(0),
+        };
+        assert_eq!(astar(&g, 0, 4, |_| 0), None);
+        assert_eq!(g.expanded.get(), 4);
+    }
+}
@@ -0,0 +1,206 @@
+//! Integers with an upper bound below the range of their primitive type.
+//!
+//! Useful for protocol-limited ids in combination with
+//! [`idx_newtype!`](crate::idx_newtype). For [`#[derive(Idx)]`](crate::Idx),
+//! use `#[indexland(max = N)]` instead.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, bounded_idx::BoundedIdx, idx_newtype};
+//!
+//! idx_newtype! {
+//!     pub struct ChannelId(BoundedIdx<u16, 4095>);
+//! }
+//!
+//! assert_eq!(ChannelId::MAX_USIZE, 4095);
+//! assert!(ChannelId::try_from_usize(4096).is_err());
+//! ```
+//!
+//! ## Implementations
+//! - [`BoundedIdx<u8, MAX>`]
+//! - [`BoundedIdx<u16, MAX>`]
+//! - [`BoundedIdx<u32, MAX>`]
+//! - [`BoundedIdx<u64, MAX>`]
+//! - [`BoundedIdx<usize, MAX>`]
+
+use core::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Rem, RemAssign, Sub, SubAssign},
+};
+
+use crate::{Idx, IdxOutOfRange, idx::IdxConst};
+
+/// An unsigned integer that is guaranteed to be at most `MAX`.
+///
+/// `MAX` must fit into the primitive, otherwise using the index fails
+/// to compile:
+/// ```compile_fail
+/// use indexland::{BoundedIdx, Idx};
+///
+/// let _ = BoundedIdx::<u8, 300>::from_usize(299);
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct BoundedIdx<P, const MAX: usize>(P);
+
+impl<P: Copy, const MAX: usize> BoundedIdx<P, MAX> {
+    pub const fn get(self) -> P {
+        self.0
+    }
+}
+
+impl<P: Debug, const MAX: usize> Debug for BoundedIdx<P, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<P: Display, const MAX: usize> Display for BoundedIdx<P, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+macro_rules! bounded_idx_impl {
+    ($($primitive: ident),*) => {$(
+        impl<const MAX: usize> BoundedIdx<$primitive, MAX> {
+            #[allow(clippy::cast_possible_truncation)]
+            const MAX_PRIMITIVE: $primitive = {
+                assert!(
+                    MAX as u128 <= $primitive::MAX as u128,
+                    "BoundedIdx MAX exceeds the range of its primitive"
+                );
+                MAX as $primitive
+            };
+
+            /// Returns `None` if `v` is larger than `MAX`.
+            pub const fn new(v: $primitive) -> Option<Self> {
+                if v > Self::MAX_PRIMITIVE {
+                    return None;
+                }
+                Some(BoundedIdx(v))
+            }
+        }
+
+        impl<const MAX: usize> IdxConst<BoundedIdx<$primitive, MAX>> {
+            pub const fn from_usize(v: usize) -> BoundedIdx<$primitive, MAX> {
+                match BoundedIdx::<$primitive, MAX>::new(IdxConst::<$primitive>::from_usize(v)) {
+                    Some(v) => v,
+                    None => panic!("index out of range"),
+                }
+            }
+            pub const fn into_usize(v: BoundedIdx<$primitive, MAX>) -> usize {
+                IdxConst::<$primitive>::into_usize(v.0)
+            }
+        }
+
+        impl<const MAX: usize> Idx for BoundedIdx<$primitive, MAX> {
+            const ZERO: Self = BoundedIdx(0);
+            const ONE: Self = BoundedIdx(1);
+            const MAX: Self = BoundedIdx(Self::MAX_PRIMITIVE);
+            // goes through `MAX_PRIMITIVE` to enforce its range check
+            #[allow(clippy::cast_possible_truncation)]
+            const MAX_USIZE: usize = Self::MAX_PRIMITIVE as usize;
+
+            #[inline(always)]
+            fn from_usize(v: usize) -> Self {
+                match Self::try_from_usize(v) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{e}"),
+                }
+            }
+            #[inline(always)]
+            fn into_usize(self) -> usize {
+                <$primitive as Idx>::into_usize(self.0)
+            }
+            #[inline(always)]
+            fn from_usize_unchecked(v: usize) -> Self {
+                let _ = Self::MAX_PRIMITIVE;
+                BoundedIdx(<$primitive as Idx>::from_usize_unchecked(v))
+            }
+            #[inline(always)]
+            fn into_usize_unchecked(self) -> usize {
+                <$primitive as Idx>::into_usize_unchecked(self.0)
+            }
+            #[inline(always)]
+            fn try_into_usize(self) -> Option<usize> {
+                <$primitive as Idx>::try_into_usize(self.0)
+            }
+            fn wrapping_add(self, other: Self) -> Self {
+                let (a, b) = (self.into_usize(), other.into_usize());
+                let room = Self::MAX_USIZE - a;
+                Self::from_usize_unchecked(if b <= room { a + b } else { b - room - 1 })
+            }
+            fn wrapping_sub(self, other: Self) -> Self {
+                let (a, b) = (self.into_usize(), other.into_usize());
+                Self::from_usize_unchecked(if b <= a { a - b } else { Self::MAX_USIZE - (b - a - 1) })
+            }
+            #[inline]
+            fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+                if v > Self::MAX_USIZE {
+                    return Err(IdxOutOfRange::new::<Self>(v));
+                }
+                Ok(Self::from_usize_unchecked(v))
+            }
+        }
+
+        impl<const MAX: usize> Add for BoundedIdx<$primitive, MAX> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self::from_usize(self.into_usize() + rhs.into_usize())
+            }
+        }
+
+        impl<const MAX: usize> Sub for BoundedIdx<$primitive, MAX> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                BoundedIdx(self.0 - rhs.0)
+            }
+        }
+
+        impl<const MAX: usize> Rem for BoundedIdx<$primitive, MAX> {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self {
+                BoundedIdx(self.0 % rhs.0)
+            }
+        }
+
+        impl<const MAX: usize> AddAssign for BoundedIdx<$primitive, MAX> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const MAX: usize> SubAssign for BoundedIdx<$primitive, MAX> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const MAX: usize> RemAssign for BoundedIdx<$primitive, MAX> {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+    )*};
+}
+
+bounded_idx_impl![u8, u16, u32, u64, usize];
+
+#[cfg(test)]
+mod test {
+    use crate::Idx;
+
+    use super::BoundedIdx;
+
+    #[test]
+    fn wrapping_respects_bound() {
+        type B = BoundedIdx<u8, 9>;
+        assert_eq!(B::MAX.get(), 9);
+        assert_eq!(B::new(10), None);
+        assert_eq!(B::from_usize(7).wrapping_add(B::from_usize(5)).get(), 2);
+        assert_eq!(B::from_usize(2).wrapping_sub(B::from_usize(5)).get(), 7);
+        assert_eq!(B::from_usize(7).saturating_add(B::from_usize(5)), B::MAX);
+        assert_eq!(B::from_usize(7).checked_add(B::from_usize(3)), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! Graphviz DOT export for graphs over typed node ids.
+//!
+//! Nodes are named after the [`Display`] output of their id and are emitted
+//! in index order, followed by the edges in adjacency order, so the output
+//! is deterministic and can be snapshot tested.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexVec, dot::Dot, index_vec};
+//!
+//! #[derive(Idx)]
+//! struct BlockId(u32);
+//!
+//! let cfg: IndexVec<BlockId, Vec<BlockId>> = index_vec![
+//!     vec![BlockId(1), BlockId(2)],
+//!     vec![BlockId(2)],
+//!     vec![],
+//! ];
+//!
+//! let dot = Dot::new(&cfg)
+//!     .graph_name("cfg")
+//!     .node_label(|b| format!("bb{b}"))
+//!     .edge_attrs(|from, _to| {
+//!         if from == BlockId(0) { vec![("style", "dashed".into())] } else { vec![] }
+//!     });
+//!
+//! assert_eq!(
+//!     dot.to_string(),
+//!     r#"digraph "cfg" {
+//!     "0" [label="bb0"];
+//!     "1" [label="bb1"];
+//!     "2" [label="bb2"];
+//!     "0" -> "1" [style="dashed"];
+//!     "0" -> "2" [style="dashed"];
+//!     "1" -> "2";
+//! }
+//! "#
+//! );
+//! ```
+
+use core::fmt::{self, Display, Write};
+
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{Idx, IndexSlice, algo::Adjacency};
+
+type NodeFn<'a, N, R> = Box<dyn Fn(N) -> R + 'a>;
+type EdgeFn<'a, N, R> = Box<dyn Fn(N, N) -> R + 'a>;
+
+/// Attributes as `(name, value)` pairs. Values are quoted and escaped.
+pub type Attrs = Vec<(&'static str, String)>;
+
+struct Cluster<N> {
+    label: String,
+    nodes: Vec<N>,
+}
+
+/// A directed graph ready to be rendered as DOT through its [`Display`]
+/// implementation or [`Dot::write`].
+pub struct Dot<'a, N> {
+    name: String,
+    node_count: usize,
+    edges: Vec<(N, N)>,
+    graph_attrs: Attrs,
+    clusters: BTreeMap<usize, Cluster<N>>,
+    node_label: Option<NodeFn<'a, N, String>>,
+    node_attrs: Option<NodeFn<'a, N, Attrs>>,
+    edge_label: Option<EdgeFn<'a, N, String>>,
+    edge_attrs: Option<EdgeFn<'a, N, Attrs>>,
+}
+
+impl<'a, N: Idx + Display> Dot<'a, N> {
+    /// Captures the edges of any [`Adjacency`], e.g. an
+    /// `IndexVec<N, Vec<N>>` or an [`IndexGraph`](crate::IndexGraph).
+    pub fn new<G: Adjacency<N> + ?Sized>(graph: &G) -> Self {
+        let node_count = graph.node_count();
+        let edges = (0..node_count)
+            .map(N::from_usize)
+            .flat_map(|n| graph.neighbors(n).map(move |m| (n, m)))
+            .collect();
+        Self::from_edges(node_count, edges)
+    }
+
+    /// Captures the edges of an adjacency list whose entries can be iterated
+    /// by reference, like `Vec<N>`, `[N; K]` or `Option<N>`.
+    pub fn from_index_slice<T>(graph: &IndexSlice<N, T>) -> Self
+    where
+        for<'b> &'b T: IntoIterator<Item = &'b N>,
+    {
+        let edges = graph
+            .iter_enumerated()
+            .flat_map(|(n, succs)| succs.into_iter().map(move |&m| (n, m)))
+            .collect();
+        Self::from_edges(graph.len(), edges)
+    }
+
+    fn from_edges(node_count: usize, edges: Vec<(N, N)>) -> Self {
+        Self {
+            name: String::new(),
+            node_count,
+            edges,
+            graph_attrs: Vec::new(),
+            clusters: BTreeMap::new(),
+            node_label: None,
+            node_attrs: None,
+            edge_label: None,
+            edge_attrs: None,
+        }
+    }
+
+    pub fn graph_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+    pub fn graph_attrs(mut self, attrs: Attrs) -> Self {
+        self.graph_attrs = attrs;
+        self
+    }
+    pub fn node_label(mut self, f: impl Fn(N) -> String + 'a) -> Self {
+        self.node_label = Some(Box::new(f));
+        self
+    }
+    pub fn node_attrs(mut self, f: impl Fn(N) -> Attrs + 'a) -> Self {
+        self.node_attrs = Some(Box::new(f));
+        self
+    }
+    pub fn edge_label(mut self, f: impl Fn(N, N) -> String + 'a) -> Self {
+        self.edge_label = Some(Box::new(f));
+        self
+    }
+    pub fn edge_attrs(mut self, f: impl Fn(N, N) -> Attrs + 'a) -> Self {
+        self.edge_attrs = Some(Box::new(f));
+        self
+    }
+
+    /// Groups every node into the cluster given by `cluster_of`.
+    /// Clusters are emitted in ascending order of their id. Entries past
+    /// the node count of the graph are ignored.
+    pub fn clusters<C: Idx>(
+        self,
+        cluster_of: &IndexSlice<N, C>,
+        label: impl Fn(C) -> String,
+    ) -> Self {
+        self.partial_clusters_impl(
+            cluster_of.iter_enumerated().map(|(n, &c)| (n, Some(c))),
+            label,
+        )
+    }
+
+    /// Like [`clusters`](Self::clusters), but nodes mapped to `None` are not
+    /// part of any cluster.
+    pub fn partial_clusters<C: Idx>(
+        self,
+        cluster_of: &IndexSlice<N, Option<C>>,
+        label: impl Fn(C) -> String,
+    ) -> Self {
+        self.partial_clusters_impl(cluster_of.iter_enumerated().map(|(n, &c)| (n, c)), label)
+    }
+
+    fn partial_clusters_impl<C: Idx>(
+        mut self,
+        cluster_of: impl Iterator<Item = (N, Option<C>)>,
+        label: impl Fn(C) -> String,
+    ) -> Self {
+        self.clusters.clear();
+        for (node, cluster) in cluster_of.take(self.node_count) {
+            let Some(cluster) = cluster else {
+                continue;
+            };
+            self.clusters
+                .entry(cluster.into_usize())
+                .or_insert_with(|| Cluster {
+                    label: label(cluster),
+                    nodes: Vec::new(),
+                })
+                .nodes
+                .push(node);
+        }
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        write!(w, "{self}")
+    }
+
+    fn fmt_node(&self, f: &mut fmt::Formatter<'_>, node: N, indent: &str) -> fmt::Result {
+        f.write_str(indent)?;
+        write_quoted(f, &node.to_string())?;
+        let label = self.node_label.as_ref().map(|l| l(node));
+        let attrs = self.node_attrs.as_ref().map(|a| a(node));
+        write_attrs(f, label.as_deref(), attrs.as_deref().unwrap_or_default())?;
+        f.write_str(";\n")
+    }
+}
+
+impl<N: Idx + Display> Display for Dot<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("digraph ")?;
+        write_quoted(f, &self.name)?;
+        f.write_str(" {\n")?;
+        for (name, value) in &self.graph_attrs {
+            write!(f, "    {name}=")?;
+            write_quoted(f, value)?;
+            f.write_str(";\n")?;
+        }
+
+        let mut clustered = alloc::vec![false; self.node_count];
+        for (id, cluster) in &self.clusters {
+            writeln!(f, "    subgraph \"cluster_{id}\" {{")?;
+            f.write_str("        label=")?;
+            write_quoted(f, &cluster.label)?;
+            f.write_str(";\n")?;
+            for &node in &cluster.nodes {
+                clustered[node.into_usize()] = true;
+                self.fmt_node(f, node, "        ")?;
+            }
+            f.write_str("    }\n")?;
+        }
+        for node in (0..self.node_count).filter(|&n| !clustered[n]) {
+            self.fmt_node(f, N::from_usize(node), "    ")?;
+        }
+
+        for &(from, to) in &self.edges {
+            f.write_str("    ")?;
+            write_quoted(f, &from.to_string())?;
+            f.write_str(" -> ")?;
+            write_quoted(f, &to.to_string())?;
+            let label = self.edge_label.as_ref().map(|l| l(from, to));
+            let attrs = self.edge_attrs.as_ref().map(|a| a(from, to));
+            write_attrs(f, label.as_deref(), attrs.as_deref().unwrap_or_default())?;
+            f.write_str(";\n")?;
+        }
+        f.write_str("}\n")
+    }
+}
+
+fn write_attrs(
+    f: &mut fmt::Formatter<'_>,
+    label: Option<&str>,
+    attrs: &[(&'static str, String)],
+) -> fmt::Result {
+    let mut attrs = label
+        .map(|l| ("label", l))
+        .into_iter()
+        .chain(attrs.iter().map(|(k, v)| (*k, v.as_str())))
+        .peekable();
+    if attrs.peek().is_none() {
+        return Ok(());
+    }
+    f.write_str(" [")?;
+    for (i, (name, value)) in attrs.enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{name}=")?;
+        write_quoted(f, value)?;
+    }
+    f.write_char(']')
+}
+
+/// Writes `s` as a DOT string, escaping quotes and newlines.
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{format, string::ToString, vec};
+
+    use crate::{IndexVec, index_vec};
+
+    use super::Dot;
+
+    #[test]
+    fn clusters_and_escaping() {
+        let succs: IndexVec<u32, Option<u32>> = index_vec![Some(1), Some(2), None, Some(0)];
+        // the extra entry is past the node count and ignored
+        let regions: IndexVec<u32, Option<u8>> =
+            index_vec![Some(1), None, Some(0), Some(1), Some(2)];
+        let dot = Dot::from_index_slice(&succs)
+            .graph_attrs(vec![("rankdir", "LR".into())])
+            .partial_clusters(&regions, |r| format!("region {r}"))
+            .node_attrs(|n| {
+                if n == 2 {
+                    vec![("shape", "box".into())]
+                } else {
+                    vec![]
+                }
+            })
+            .edge_label(|_, to| {
+                if to == 0 {
+                    "\"back\"".into()
+                } else if to == 2 {
+                    "C:\\".into()
+                } else {
+                    "fall".into()
+                }
+            });
+        assert_eq!(
+            dot.to_string(),
+            r#"digraph "" {
+    rankdir="LR";
+    subgraph "cluster_0" {
+        label="region 0";
+        "2" [shape="box"];
+    }
+    subgraph "cluster_1" {
+        label="region 1";
+        "0";
+        "3";
+    }
+    "1";
+    "0" -> "1" [label="fall"];
+    "1" -> "2" [label="C:\\"];
+    "3" -> "0" [label="\"back\""];
+}
+"#
+        );
+    }
+}
@@ -20,6 +20,12 @@ pub trait Idx: 'static + Copy {
     fn into_usize(self) -> usize;
     fn into_usize_unchecked(self) -> usize;
 
+    /// Like [`into_usize`](Idx::into_usize), but returns `None` instead of
+    /// panicking for values that don't fit, e.g. negative ones.
+    fn try_into_usize(self) -> Option<usize> {
+        Some(self.into_usize())
+    }
+
     /// Careful with signed integers as this might make them negative.
     ///
     /// That would cause the next `into_usize` conversion to panic.
@@ -44,6 +50,100 @@ pub trait Idx: 'static + Copy {
                 .min(Self::MAX_USIZE),
         )
     }
+
+    /// Like [`from_usize`](Self::from_usize), but fails instead of
+    /// panicking or truncating if `v` is not a valid index of this type.
+    fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+        if v > Self::MAX_USIZE {
+            return Err(IdxOutOfRange::new::<Self>(v));
+        }
+        Ok(Self::from_usize(v))
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let sum = self.into_usize().checked_add(other.into_usize())?;
+        Self::try_from_usize(sum).ok()
+    }
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        let diff = self.into_usize().checked_sub(other.into_usize())?;
+        Self::try_from_usize(diff).ok()
+    }
+    /// Moves the index by `offset` in either direction, returning `None` if
+    /// the result is not a valid index of this type.
+    fn checked_offset(self, offset: isize) -> Option<Self> {
+        let v = self.into_usize().checked_add_signed(offset)?;
+        Self::try_from_usize(v).ok()
+    }
+    /// The absolute difference between two indices.
+    fn distance(self, other: Self) -> usize {
+        self.into_usize().abs_diff(other.into_usize())
+    }
+    /// The signed offset from `origin` to `self`, or `None` if it does not
+    /// fit into an `isize`.
+    fn checked_delta(self, origin: Self) -> Option<isize> {
+        let (a, b) = (self.into_usize(), origin.into_usize());
+        if a >= b {
+            isize::try_from(a - b).ok()
+        } else {
+            0isize.checked_sub_unsigned(b - a)
+        }
+    }
+}
+
+/// A signed offset between two indices, as generated by
+/// `#[indexland(delta = ..)]`.
+pub trait IdxDelta: 'static + Copy + Ord {
+    const ZERO: Self;
+    fn from_isize(v: isize) -> Self;
+    fn into_isize(self) -> isize;
+}
+
+impl IdxDelta for isize {
+    const ZERO: Self = 0;
+    #[inline(always)]
+    fn from_isize(v: isize) -> Self {
+        v
+    }
+    #[inline(always)]
+    fn into_isize(self) -> isize {
+        self
+    }
+}
+
+/// Error returned by [`Idx::try_from_usize`] when a value does not fit into
+/// an index type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdxOutOfRange {
+    type_name: &'static str,
+    value: usize,
+}
+
+impl IdxOutOfRange {
+    pub fn new<I: ?Sized>(value: usize) -> Self {
+        Self {
+            type_name: core::any::type_name::<I>(),
+            value,
+        }
+    }
+    /// The name of the index type that `value` did not fit into.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    pub fn value(&self) -> usize {
+        self.value
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdxOutOfRange {}
+
+impl core::fmt::Display for IdxOutOfRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "index {} is out of range for {}",
+            self.value, self.type_name
+        )
+    }
 }
 
 pub trait IdxEnum: Idx {
@@ -58,9 +158,46 @@ pub trait IdxEnum: Idx {
     /// Please make sure to honor this when implementing this trait manually.
     type EnumIndexArray<T>; // = `IndexArray<Self, T, { Self::VARIANT_COUNT }>`
 
+    /// The names of [`VARIANTS`](Self::VARIANTS), in the same order.
+    const VARIANT_NAMES: &'static [&'static str];
+
     fn iter() -> core::iter::Copied<core::slice::Iter<'static, Self>> {
         Self::VARIANTS.iter().copied()
     }
+    fn name(self) -> &'static str {
+        Self::VARIANT_NAMES[self.into_usize()]
+    }
+    fn from_name(name: &str) -> Option<Self> {
+        let pos = Self::VARIANT_NAMES.iter().position(|n| *n == name)?;
+        Some(Self::VARIANTS[pos])
+    }
+}
+
+/// Error returned by the derived [`FromStr`](core::str::FromStr)
+/// implementation of [`IdxEnum`]s for unknown variant names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseIdxEnumError {
+    type_name: &'static str,
+}
+
+impl ParseIdxEnumError {
+    pub fn new<E: ?Sized>() -> Self {
+        Self {
+            type_name: core::any::type_name::<E>(),
+        }
+    }
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIdxEnumError {}
+
+impl core::fmt::Display for ParseIdxEnumError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown variant name for {}", self.type_name)
+    }
 }
 
 pub trait IdxNewtype: Idx {
@@ -129,6 +266,22 @@ impl Idx for usize {
     fn saturating_sub(self, other: Self) -> Self {
         self.saturating_sub(other)
     }
+    #[inline(always)]
+    fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+        Ok(v)
+    }
+    #[inline(always)]
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+    #[inline(always)]
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
+    #[inline(always)]
+    fn checked_offset(self, offset: isize) -> Option<Self> {
+        self.checked_add_signed(offset)
+    }
 }
 
 macro_rules! primitive_idx_implemenation_unsized {
@@ -183,6 +336,22 @@ macro_rules! primitive_idx_implemenation_unsized {
             fn saturating_sub(self, other: Self) -> Self {
                 $primitive::saturating_sub(self, other)
             }
+            #[inline]
+            fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+                $primitive::try_from(v).map_err(|_| IdxOutOfRange::new::<Self>(v))
+            }
+            #[inline]
+            fn try_into_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+            #[inline(always)]
+            fn checked_add(self, other: Self) -> Option<Self> {
+                $primitive::checked_add(self, other)
+            }
+            #[inline(always)]
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                $primitive::checked_sub(self, other)
+            }
         }
     )*};
 }
@@ -235,6 +404,14 @@ macro_rules! primitive_idx_implemenation_sized {
             fn saturating_sub(self, other: Self) -> Self {
                 $primitive::saturating_sub(self, other)
             }
+            #[inline]
+            fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+                $primitive::try_from(v).map_err(|_| IdxOutOfRange::new::<Self>(v))
+            }
+            #[inline]
+            fn try_into_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
         }
     )*};
 }
@@ -242,11 +419,156 @@ macro_rules! primitive_idx_implemenation_sized {
 primitive_idx_implemenation_unsized![u8, u16, u32, u64, u128];
 primitive_idx_implemenation_sized![isize, i8, i16, i32, i64, i128];
 
+// Index `n` is stored as `n + 1`, so that `Option<I>` is free and the
+// representation stays compatible with C APIs that use 0 for "none".
+macro_rules! nonzero_idx_implemenation {
+    ($($primitive: ident),*) => {$(
+        impl Idx for core::num::NonZero<$primitive> {
+            const ZERO: Self = core::num::NonZero::<$primitive>::MIN;
+            const ONE: Self = core::num::NonZero::<$primitive>::MIN.saturating_add(1);
+            const MAX: Self = core::num::NonZero::<$primitive>::MAX;
+
+            #[allow(clippy::cast_possible_truncation)]
+            const MAX_USIZE: usize = ($primitive::MAX - 1) as usize;
+
+            #[inline(always)]
+            fn into_usize(self) -> usize {
+                <$primitive as Idx>::into_usize(self.get() - 1)
+            }
+            #[inline(always)]
+            fn from_usize(v: usize) -> Self {
+                match Self::try_from_usize(v) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{e}"),
+                }
+            }
+            #[inline(always)]
+            fn into_usize_unchecked(self) -> usize {
+                <$primitive as Idx>::into_usize_unchecked(self.get() - 1)
+            }
+            #[inline]
+            fn try_into_usize(self) -> Option<usize> {
+                <$primitive as Idx>::try_into_usize(self.get() - 1)
+            }
+            #[inline(always)]
+            fn from_usize_unchecked(v: usize) -> Self {
+                // `MAX_USIZE + 1` wraps around to `ZERO`
+                core::num::NonZero::new(<$primitive as Idx>::from_usize_unchecked(v).wrapping_add(1))
+                    .unwrap_or(Self::ZERO)
+            }
+            fn wrapping_add(self, other: Self) -> Self {
+                let (a, b) = (self.get() - 1, other.get() - 1);
+                let room = $primitive::MAX - 1 - a;
+                let v = if b <= room { a + b } else { b - room - 1 };
+                core::num::NonZero::new(v + 1).unwrap()
+            }
+            fn wrapping_sub(self, other: Self) -> Self {
+                let (a, b) = (self.get() - 1, other.get() - 1);
+                let v = if b <= a { a - b } else { $primitive::MAX - 1 - (b - a - 1) };
+                core::num::NonZero::new(v + 1).unwrap()
+            }
+            #[inline]
+            fn saturating_add(self, other: Self) -> Self {
+                core::num::NonZero::<$primitive>::saturating_add(self, other.get() - 1)
+            }
+            #[inline]
+            fn saturating_sub(self, other: Self) -> Self {
+                core::num::NonZero::new(self.get().saturating_sub(other.get() - 1)).unwrap_or(Self::ZERO)
+            }
+            #[inline]
+            fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+                $primitive::try_from(v)
+                    .ok()
+                    .and_then(|v| v.checked_add(1))
+                    .and_then(core::num::NonZero::new)
+                    .ok_or_else(|| IdxOutOfRange::new::<Self>(v))
+            }
+            #[inline]
+            fn checked_add(self, other: Self) -> Option<Self> {
+                core::num::NonZero::<$primitive>::checked_add(self, other.get() - 1)
+            }
+            #[inline]
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                self.get().checked_sub(other.get() - 1).and_then(core::num::NonZero::new)
+            }
+        }
+    )*};
+}
+
+nonzero_idx_implemenation![u8, u16, u32, u64, usize];
+
+/// Const counterparts of [`Idx::from_usize`] and [`Idx::into_usize`],
+/// used by the `from_usize_const` / `into_usize_const` methods generated
+/// by the derives and [`idx_newtype!`](crate::idx_newtype).
+///
+/// Trait methods can't be called in const contexts, so this dispatches on
+/// the concrete base type through inherent impls instead.
+#[doc(hidden)]
+pub struct IdxConst<I>(core::marker::PhantomData<I>);
+
+macro_rules! idx_const_implementation_unsigned {
+    ($($primitive: ident),*) => {$(
+        impl IdxConst<$primitive> {
+            #[allow(clippy::cast_possible_truncation)]
+            pub const fn from_usize(v: usize) -> $primitive {
+                assert!(v as u128 <= $primitive::MAX as u128, "index out of range");
+                v as $primitive
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            pub const fn into_usize(v: $primitive) -> usize {
+                assert!(v as u128 <= usize::MAX as u128, "index out of range");
+                v as usize
+            }
+        }
+        impl IdxConst<core::num::NonZero<$primitive>> {
+            pub const fn from_usize(v: usize) -> core::num::NonZero<$primitive> {
+                let v = IdxConst::<$primitive>::from_usize(v);
+                assert!(v < $primitive::MAX, "index out of range");
+                match core::num::NonZero::new(v + 1) {
+                    Some(v) => v,
+                    None => unreachable!(),
+                }
+            }
+            pub const fn into_usize(v: core::num::NonZero<$primitive>) -> usize {
+                IdxConst::<$primitive>::into_usize(v.get() - 1)
+            }
+        }
+    )*};
+}
+
+macro_rules! idx_const_implementation_signed {
+    ($($primitive: ident),*) => {$(
+        impl IdxConst<$primitive> {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            pub const fn from_usize(v: usize) -> $primitive {
+                assert!(v as u128 <= $primitive::MAX as u128, "index out of range");
+                v as $primitive
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            pub const fn into_usize(v: $primitive) -> usize {
+                assert!(
+                    v >= 0 && v as u128 <= usize::MAX as u128,
+                    "index out of range"
+                );
+                v as usize
+            }
+        }
+    )*};
+}
+
+idx_const_implementation_unsigned![u8, u16, u32, u64, u128, usize];
+idx_const_implementation_signed![isize, i8, i16, i32, i64, i128];
+
 /// Declarative alternative to [`#[derive(IdxNewtype)]`](indexland_derive::IdxNewtype).
 ///
 /// Allows generating multiple indices at once and does not require
 /// proc-macros.
 ///
+/// Starting the invocation with `#![extra(Const)]` additionally
+/// generates inherent `const fn from_usize_const` and `into_usize_const`.
+/// These require the base type to be an integer primitive,
+/// [`NonZero`](core::num::NonZero) or [`BoundedIdx`](crate::BoundedIdx).
+///
 /// # Example
 /// ```rust
 /// # use indexland::idx_newtype;
@@ -254,9 +576,36 @@ primitive_idx_implemenation_sized![isize, i8, i16, i32, i64, i128];
 ///     struct FooId(usize);
 ///     struct BarId(u32);
 /// }
+///
+/// idx_newtype! {
+///     #![extra(Const)]
+///     struct BazId(u32);
+/// }
+///
+/// const BAZ: BazId = BazId::from_usize_const(3);
 /// ```
 #[macro_export]
 macro_rules! idx_newtype {
+    {
+        #![extra(Const)]
+        $( $(#[$attrs: meta])* $type_vis: vis struct $name: ident ($base_vis: vis $base_type: path); )*
+    } => {
+        $crate::idx_newtype! {
+            $( $(#[$attrs])* $type_vis struct $name ($base_vis $base_type); )*
+        }
+        $(
+            impl $name {
+                #[allow(dead_code)]
+                $type_vis const fn from_usize_const(v: usize) -> Self {
+                    $name($crate::idx::IdxConst::<$base_type>::from_usize(v))
+                }
+                #[allow(dead_code)]
+                $type_vis const fn into_usize_const(self) -> usize {
+                    $crate::idx::IdxConst::<$base_type>::into_usize(self.0)
+                }
+            }
+        )*
+    };
     { $( $(#[$attrs: meta])* $type_vis: vis struct $name: ident ($base_vis: vis $base_type: path); )* } => {$(
         $(#[$attrs])*
         #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -285,6 +634,9 @@ macro_rules! idx_newtype {
             fn into_usize_unchecked(self) -> usize {
                 <$base_type as $crate::Idx>::into_usize_unchecked(self.0)
             }
+            fn try_into_usize(self) -> ::core::option::Option<usize> {
+                <$base_type as $crate::Idx>::try_into_usize(self.0)
+            }
             fn saturating_add(self, other: Self) -> Self {
                $name(<$base_type as  $crate::Idx>::saturating_add(self.0, other.0))
             }
@@ -297,6 +649,26 @@ macro_rules! idx_newtype {
             fn wrapping_sub(self, other: Self) -> Self {
                 $name(<$base_type as $crate::Idx>::wrapping_sub(self.0, other.0))
             }
+            fn try_from_usize(v: usize) -> ::core::result::Result<Self, $crate::idx::IdxOutOfRange> {
+                match <$base_type as $crate::Idx>::try_from_usize(v) {
+                    ::core::result::Result::Ok(v) => ::core::result::Result::Ok($name(v)),
+                    ::core::result::Result::Err(_) => ::core::result::Result::Err(
+                        $crate::idx::IdxOutOfRange::new::<Self>(v)
+                    ),
+                }
+            }
+            fn checked_add(self, other: Self) -> ::core::option::Option<Self> {
+                <$base_type as $crate::Idx>::checked_add(self.0, other.0).map($name)
+            }
+            fn checked_sub(self, other: Self) -> ::core::option::Option<Self> {
+                <$base_type as $crate::Idx>::checked_sub(self.0, other.0).map($name)
+            }
+            fn checked_offset(self, offset: isize) -> ::core::option::Option<Self> {
+                <$base_type as $crate::Idx>::checked_offset(self.0, offset).map($name)
+            }
+            fn distance(self, other: Self) -> usize {
+                <$base_type as $crate::Idx>::distance(self.0, other.0)
+            }
         }
         impl $crate::IdxNewtype for $name {
             type Base = $base_type;
@@ -373,11 +745,272 @@ macro_rules! idx_newtype {
     )*};
 }
 
+/// Declarative alternative to [`#[derive(IdxEnum)]`](indexland_derive::IdxEnum).
+///
+/// Generates the same default traits as [`#[derive(Idx)]`](indexland_derive::Idx).
+/// Explicit discriminants are not supported.
+///
+/// Like the attributes of the derive, the invocation may start with
+/// - `#![extra(..)]`: any of `Display`, `FromStr` and `Const`
+///   (inherent `const fn from_usize_const` and `into_usize_const`).
+/// - `#![arith = "full"]`: additionally implement
+///   [`Mul`](core::ops::Mul), [`Div`](core::ops::Div),
+///   [`Rem`](core::ops::Rem) and their assigning variants.
+///
+/// # Example
+/// ```rust
+/// # use indexland::{idx_enum, EnumIndexArray, IndexArray};
+/// idx_enum! {
+///     pub enum Color {
+///         Red,
+///         Green,
+///         Blue,
+///     }
+/// }
+///
+/// let hex: EnumIndexArray<Color, &str> = IndexArray::new(["#f00", "#0f0", "#00f"]);
+/// assert_eq!(hex[Color::Green], "#0f0");
+///
+/// idx_enum! {
+///     #![extra(Display, FromStr)]
+///     enum Suit { Hearts, Spades }
+/// }
+///
+/// assert_eq!("Spades".parse::<Suit>().unwrap().to_string(), "Spades");
+/// ```
+#[macro_export]
+macro_rules! idx_enum {
+    (@munch [$($flags: tt)*]) => {};
+    (@munch [$($flags: tt)*]
+        $(#[$attrs: meta])*
+        $type_vis: vis enum $name: ident {
+            $( $(#[$variant_attrs: meta])* $variant: ident ),+ $(,)?
+        }
+        $($rest: tt)*
+    ) => {
+        $(#[$attrs])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        $type_vis enum $name {
+            $( $(#[$variant_attrs])* $variant ),+
+        }
+
+        impl $crate::IdxEnum for $name {
+            const VARIANT_COUNT: usize = <Self as $crate::IdxEnum>::VARIANTS.len();
+            const VARIANTS: &'static [Self] = &[ $($name::$variant),+ ];
+            const VARIANT_NAMES: &'static [&'static str] = &[ $(stringify!($variant)),+ ];
+            type EnumIndexArray<T> =
+                $crate::index_array::IndexArray<Self, T, { <Self as $crate::IdxEnum>::VARIANT_COUNT }>;
+        }
+        impl $crate::Idx for $name {
+            const ZERO: Self = <Self as $crate::IdxEnum>::VARIANTS[0];
+            // a single variant enum wraps around to itself
+            const ONE: Self = <Self as $crate::IdxEnum>::VARIANTS
+                [if <Self as $crate::IdxEnum>::VARIANT_COUNT > 1 { 1 } else { 0 }];
+            const MAX: Self =
+                <Self as $crate::IdxEnum>::VARIANTS[<Self as $crate::Idx>::MAX_USIZE];
+            const MAX_USIZE: usize = <Self as $crate::IdxEnum>::VARIANT_COUNT - 1;
+
+            #[inline]
+            fn from_usize(v: usize) -> Self {
+                match <Self as $crate::Idx>::try_from_usize(v) {
+                    ::core::result::Result::Ok(v) => v,
+                    ::core::result::Result::Err(e) => panic!("{e}"),
+                }
+            }
+            #[inline(always)]
+            fn into_usize(self) -> usize {
+                self as usize
+            }
+            #[inline]
+            fn from_usize_unchecked(v: usize) -> Self {
+                match <Self as $crate::IdxEnum>::VARIANTS.get(v) {
+                    ::core::option::Option::Some(v) => *v,
+                    ::core::option::Option::None => <Self as $crate::Idx>::ZERO,
+                }
+            }
+            #[inline(always)]
+            fn into_usize_unchecked(self) -> usize {
+                self as usize
+            }
+            fn wrapping_add(self, other: Self) -> Self {
+                let count = <Self as $crate::IdxEnum>::VARIANT_COUNT;
+                <Self as $crate::Idx>::from_usize_unchecked(
+                    (self as usize + other as usize) % count,
+                )
+            }
+            fn wrapping_sub(self, other: Self) -> Self {
+                let count = <Self as $crate::IdxEnum>::VARIANT_COUNT;
+                <Self as $crate::Idx>::from_usize_unchecked(
+                    (self as usize + count - other as usize) % count,
+                )
+            }
+            #[inline]
+            fn try_from_usize(v: usize) -> ::core::result::Result<Self, $crate::idx::IdxOutOfRange> {
+                match <Self as $crate::IdxEnum>::VARIANTS.get(v) {
+                    ::core::option::Option::Some(v) => ::core::result::Result::Ok(*v),
+                    ::core::option::Option::None => ::core::result::Result::Err(
+                        $crate::idx::IdxOutOfRange::new::<Self>(v)
+                    ),
+                }
+            }
+        }
+        impl ::core::default::Default for $name {
+            #[inline]
+            fn default() -> Self {
+                <Self as $crate::Idx>::ZERO
+            }
+        }
+        impl ::core::convert::From<usize> for $name {
+            #[inline]
+            fn from(v: usize) -> $name {
+                <$name as $crate::Idx>::from_usize(v)
+            }
+        }
+        impl ::core::convert::From<$name> for usize {
+            #[inline(always)]
+            fn from(v: $name) -> usize {
+                v as usize
+            }
+        }
+        $crate::idx_enum!(@arith $name, Add, add, AddAssign, add_assign, +);
+        $crate::idx_enum!(@arith $name, Sub, sub, SubAssign, sub_assign, -);
+        $crate::idx_enum!(@flags [$type_vis] $name; $($flags)*);
+
+        $crate::idx_enum!(@munch [$($flags)*] $($rest)*);
+    };
+    (@flags [$type_vis: vis] $name: ident;) => {};
+    (@flags [$type_vis: vis] $name: ident; #![arith = "basic"] $($rest: tt)*) => {
+        $crate::idx_enum!(@flags [$type_vis] $name; $($rest)*);
+    };
+    (@flags [$type_vis: vis] $name: ident; #![arith = "full"] $($rest: tt)*) => {
+        $crate::idx_enum!(@arith $name, Mul, mul, MulAssign, mul_assign, *);
+        $crate::idx_enum!(@arith $name, Div, div, DivAssign, div_assign, /);
+        $crate::idx_enum!(@arith $name, Rem, rem, RemAssign, rem_assign, %);
+        $crate::idx_enum!(@flags [$type_vis] $name; $($rest)*);
+    };
+    (@flags [$type_vis: vis] $name: ident; #![extra($($extra: ident),* $(,)?)] $($rest: tt)*) => {
+        $( $crate::idx_enum!(@extra [$type_vis] $name, $extra); )*
+        $crate::idx_enum!(@flags [$type_vis] $name; $($rest)*);
+    };
+    (@extra [$type_vis: vis] $name: ident, Display) => {
+        impl ::core::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(<Self as $crate::IdxEnum>::name(*self))
+            }
+        }
+    };
+    (@extra [$type_vis: vis] $name: ident, FromStr) => {
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::ParseIdxEnumError;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match <Self as $crate::IdxEnum>::from_name(s) {
+                    ::core::option::Option::Some(v) => ::core::result::Result::Ok(v),
+                    ::core::option::Option::None => ::core::result::Result::Err(
+                        $crate::ParseIdxEnumError::new::<Self>()
+                    ),
+                }
+            }
+        }
+    };
+    (@extra [$type_vis: vis] $name: ident, Const) => {
+        impl $name {
+            #[allow(dead_code)]
+            $type_vis const fn from_usize_const(v: usize) -> Self {
+                assert!(
+                    v < <Self as $crate::IdxEnum>::VARIANT_COUNT,
+                    concat!("index out of bounds for ", stringify!($name))
+                );
+                <Self as $crate::IdxEnum>::VARIANTS[v]
+            }
+            #[allow(dead_code)]
+            $type_vis const fn into_usize_const(self) -> usize {
+                self as usize
+            }
+        }
+    };
+    (@arith $name: ident, $op_trait: ident, $op_fn: ident, $assign_trait: ident, $assign_fn: ident, $op: tt) => {
+        impl ::core::ops::$op_trait for $name {
+            type Output = Self;
+            #[inline]
+            fn $op_fn(self, other: Self) -> Self {
+                <Self as $crate::Idx>::from_usize(self as usize $op other as usize)
+            }
+        }
+        impl ::core::ops::$assign_trait for $name {
+            #[inline]
+            fn $assign_fn(&mut self, other: Self) {
+                *self = ::core::ops::$op_trait::$op_fn(*self, other);
+            }
+        }
+    };
+    // collect the leading `#![..]` flags, which apply to all enums
+    (@start [$($flags: tt)*] #![$($flag: tt)*] $($rest: tt)*) => {
+        $crate::idx_enum!(@start [$($flags)* #![$($flag)*]] $($rest)*);
+    };
+    (@start [$($flags: tt)*] $($rest: tt)*) => {
+        $crate::idx_enum!(@munch [$($flags)*] $($rest)*);
+    };
+    { $($input: tt)* } => {
+        $crate::idx_enum!(@start [] $($input)*);
+    };
+}
+
 #[cfg(test)]
 mod test {
     use crate::{EnumIndexArray, IndexArray, enum_index_array, index_array};
 
-    use super::{Idx, IdxEnum};
+    use super::{Idx, IdxEnum, IdxOutOfRange};
+
+    #[test]
+    fn checked_primitives() {
+        assert_eq!(u8::try_from_usize(255), Ok(255));
+        let err = u8::try_from_usize(256).unwrap_err();
+        assert_eq!((err.type_name(), err.value()), ("u8", 256));
+        assert_eq!(i8::try_from_usize(128), Err(IdxOutOfRange::new::<i8>(128)));
+
+        assert_eq!(Idx::checked_add(250u8, 5), Some(255));
+        assert_eq!(Idx::checked_add(250u8, 6), None);
+        assert_eq!(Idx::checked_sub(3i32, 5), None);
+        assert_eq!(3u32.checked_offset(-3), Some(0));
+        assert_eq!(3u32.checked_offset(-4), None);
+        assert_eq!(Idx::distance(2usize, 7), 5);
+    }
+
+    #[test]
+    fn nonzero_offset_by_one() {
+        use core::num::NonZero;
+
+        type N = NonZero<u8>;
+        assert_eq!(N::ZERO.get(), 1);
+        assert_eq!(N::from_usize(4).get(), 5);
+        assert_eq!(N::from_usize(4).into_usize(), 4);
+        assert_eq!(N::MAX_USIZE, 254);
+        assert!(N::try_from_usize(255).is_err());
+        assert_eq!(
+            N::from_usize(250)
+                .wrapping_add(N::from_usize(10))
+                .into_usize(),
+            5
+        );
+        assert_eq!(
+            N::from_usize(2).wrapping_sub(N::from_usize(5)).into_usize(),
+            252
+        );
+        assert_eq!(
+            Idx::saturating_add(N::from_usize(250), N::from_usize(10)),
+            N::MAX
+        );
+        assert_eq!(
+            Idx::saturating_sub(N::from_usize(2), N::from_usize(5)),
+            N::ZERO
+        );
+        assert_eq!(Idx::checked_sub(N::from_usize(2), N::from_usize(3)), None);
+        assert_eq!(
+            Idx::checked_add(N::from_usize(250), N::from_usize(4)),
+            Some(N::MAX)
+        );
+    }
 
     #[test]
     fn idx_manual() {
@@ -429,6 +1062,7 @@ mod test {
         impl IdxEnum for EnumIdxManual {
             const VARIANT_COUNT: usize = 3;
             const VARIANTS: &'static [Self] = &[Self::A, Self::B, Self::B];
+            const VARIANT_NAMES: &'static [&'static str] = &["A", "B", "C"];
             type EnumIndexArray<T> = IndexArray<Self, T, { Self::VARIANT_COUNT }>;
         }
         impl Idx for EnumIdxManual {
@@ -64,6 +64,17 @@ pub type EnumIndexArray<E, T> = <E as IdxEnum>::EnumIndexArray<T>;
 ///     MyId::B => 2,
 ///     MyId::C => 3,
 /// ];
+///
+/// #[derive(Idx)]
+/// #[indexland(extra(Const))]
+/// struct NodeId(u32);
+///
+/// // newtype keys need their type to be specified,
+/// // and have to derive `extra(Const)`
+/// const QUX: IndexArray<NodeId, i32, 2> = index_array![NodeId;
+///     NodeId(1) => 2,
+///     NodeId(0) => 1,
+/// ];
 /// ```
 #[macro_export]
 macro_rules! index_array {
@@ -90,6 +101,16 @@ macro_rules! index_array {
         );
         $crate::IndexArray::new(data)
     }};
+    // for newtype indices, which can't be cast `as usize`
+    ($idx: ty; $($index:expr => $value:expr),* $(,)?) => {{
+        let keys = [ $(<$idx>::into_usize_const($index)),* ];
+        let values = [ $($value),* ];
+        let data = $crate::__private::array_from_values_and_distinct_indices(
+            keys,
+            core::mem::ManuallyDrop::new(values)
+        );
+        $crate::IndexArray::new(data)
+    }};
 }
 
 /// Create a [`EnumIndexArray`] containing the arguments.
@@ -163,6 +184,33 @@ impl<I, T, const N: usize> IndexArray<I, T, N> {
     pub const fn as_index_slice(&self) -> &IndexSlice<I, T> {
         IndexSlice::from_slice(self.data.as_slice())
     }
+    /// Const alternative to [`get`](IndexSlice::get), taking the index as `usize`.
+    ///
+    /// # Example
+    /// ```
+    /// # #![cfg(feature="derive")]
+    /// use indexland::{Idx, IndexArray, index_array};
+    ///
+    /// #[derive(Idx)]
+    /// #[indexland(extra(Const))]
+    /// struct NodeId(u32);
+    ///
+    /// const WEIGHTS: IndexArray<NodeId, u32, 3> = index_array![NodeId;
+    ///     NodeId(2) => 30,
+    ///     NodeId(0) => 10,
+    ///     NodeId(1) => 20,
+    /// ];
+    /// const W: u32 = *WEIGHTS.index_const(NodeId(1).into_usize_const());
+    /// assert_eq!(W, 20);
+    /// assert_eq!(WEIGHTS.get_const(3), None);
+    /// ```
+    pub const fn get_const(&self, index: usize) -> Option<&T> {
+        self.as_index_slice().get_const(index)
+    }
+    /// Const alternative to indexing, taking the index as `usize`.
+    pub const fn index_const(&self, index: usize) -> &T {
+        &self.data[index]
+    }
     // TODO: const once https://github.com/rust-lang/rust/issues/133333 lands
     pub fn as_mut_index_slice(&mut self) -> &mut IndexSlice<I, T> {
         IndexSlice::from_mut_slice(self.data.as_mut_slice())
@@ -180,6 +228,43 @@ impl<I, T, const N: usize> IndexArray<I, T, N> {
     {
         IndexEnumerate::new(I::ZERO, &self.data)
     }
+    /// Formats the array as a map from variant name to value.
+    ///
+    /// ## Example
+    /// ```
+    /// # #![cfg(feature="derive")]
+    /// use indexland::{EnumIndexArray, Idx, IndexArray};
+    ///
+    /// #[derive(Idx)]
+    /// enum Phase {
+    ///     Lex,
+    ///     Emit,
+    /// }
+    ///
+    /// let times: EnumIndexArray<Phase, u32> = IndexArray::new([3, 1]);
+    /// assert_eq!(format!("{:?}", times.debug_map()), "{Lex: 3, Emit: 1}");
+    /// ```
+    pub fn debug_map(&self) -> impl Debug + '_
+    where
+        I: IdxEnum,
+        T: Debug,
+    {
+        struct DebugMap<'a, I, T>(&'a [T], PhantomData<fn(I)>);
+        struct Key(&'static str);
+        impl Debug for Key {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+        impl<I: IdxEnum, T: Debug> Debug for DebugMap<'_, I, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_map()
+                    .entries(I::VARIANT_NAMES.iter().map(|&n| Key(n)).zip(self.0))
+                    .finish()
+            }
+        }
+        DebugMap::<I, T>(&self.data, PhantomData)
+    }
     pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, core::slice::IterMut<'_, T>>
     where
         I: Idx,
@@ -638,7 +723,7 @@ pub mod serde_map {
     {
         type Value = IndexArray<I, T, N>;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
             write!(formatter, "a map with {N} entries")
         }
 
@@ -664,7 +749,7 @@ pub mod serde_map {
                     Ok(Some((k, v))) => {
                         let index = k.into_usize();
                         if initialized[index] {
-                            let _ = unsafe { std::ptr::read(&raw const arr[index]).assume_init() };
+                            let _ = unsafe { core::ptr::read(&raw const arr[index]).assume_init() };
                         } else {
                             if len == N {
                                 err = Some(serde::de::Error::invalid_length(len + 1, &self));
@@ -686,7 +771,7 @@ pub mod serde_map {
                 if core::mem::needs_drop::<T>() {
                     for (i, &initialized) in initialized.iter().enumerate() {
                         if initialized {
-                            let _ = unsafe { std::ptr::read(&raw const arr[i]).assume_init() };
+                            let _ = unsafe { core::ptr::read(&raw const arr[i]).assume_init() };
                         }
                     }
                 }
@@ -0,0 +1,714 @@
+//! A fixed capacity ring buffer stored inline, without allocation.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexArrayDeque};
+//!
+//! #[derive(Idx)]
+//! struct SampleId(u8);
+//!
+//! let mut samples = IndexArrayDeque::<SampleId, i32, 3>::new();
+//! samples.push_back(1).unwrap();
+//! samples.push_back(2).unwrap();
+//! samples.push_front(0).unwrap();
+//! assert_eq!(samples.push_back(3), Err(3));
+//!
+//! assert_eq!(samples.pop_front(), Some(0));
+//! samples.push_back(3).unwrap();
+//! assert_eq!(samples[SampleId(2)], 3);
+//!
+//! let (head, tail) = samples.as_slices();
+//! assert_eq!(head.len() + tail.len(), 3);
+//! ```
+
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    iter::{Chain, FusedIterator},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Index, IndexMut},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{IdxCompat, IndexArray, IndexRange, IndexRangeBounds, index_enumerate::IndexEnumerate};
+
+use super::{idx::Idx, index_slice::IndexSlice};
+
+pub type Iter<'a, T> = Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+pub type IterMut<'a, T> = Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>;
+
+pub struct IndexArrayDeque<I, T, const CAP: usize> {
+    // physical position of the front element
+    head: usize,
+    len: usize,
+    data: [MaybeUninit<T>; CAP],
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T, const CAP: usize> IndexArrayDeque<I, T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            head: 0,
+            len: 0,
+            data: [const { MaybeUninit::uninit() }; CAP],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// unlike `From<[T; N]>::from`, this is a `const fn`
+    pub const fn from_array<const N: usize>(arr: [T; N]) -> Self {
+        pub struct AssertArrayBounds<const N: usize, const CAP: usize>;
+        impl<const N: usize, const CAP: usize> AssertArrayBounds<N, CAP> {
+            pub const OK: () = assert!(N <= CAP);
+        }
+        let _: () = AssertArrayBounds::<N, CAP>::OK;
+
+        let mut res = Self::new();
+        let src = (&raw const arr).cast::<T>();
+        let tgt = res.data.as_mut_ptr().cast::<T>();
+        unsafe {
+            core::ptr::copy_nonoverlapping(src, tgt, N);
+        }
+        core::mem::forget(arr);
+        res.len = N;
+        res
+    }
+
+    /// unlike `From<IndexArray<I, T, N>::from`, this is a `const fn`
+    pub const fn from_index_array<const N: usize>(arr: IndexArray<I, T, N>) -> Self {
+        Self::from_array(arr.into_array())
+    }
+
+    /// Physical position of the logical index `i`. `i` may be `len`.
+    const fn physical(&self, i: usize) -> usize {
+        let pos = self.head + i;
+        if pos >= CAP { pos - CAP } else { pos }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.len)
+    }
+    pub fn last_idx(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.len.checked_sub(1).map(I::from_usize)
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub const fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    pub fn get<X>(&self, index: X) -> Option<&T>
+    where
+        X: IdxCompat<I>,
+    {
+        self.get_raw(index.into_usize())
+    }
+    pub fn get_mut<X>(&mut self, index: X) -> Option<&mut T>
+    where
+        X: IdxCompat<I>,
+    {
+        self.get_raw_mut(index.into_usize())
+    }
+    fn get_raw(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        Some(unsafe { self.data[self.physical(i)].assume_init_ref() })
+    }
+    fn get_raw_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.len {
+            return None;
+        }
+        let pos = self.physical(i);
+        Some(unsafe { self.data[pos].assume_init_mut() })
+    }
+    pub fn swap(&mut self, i: I, j: I)
+    where
+        I: Idx,
+    {
+        let (i, j) = (i.into_usize(), j.into_usize());
+        assert!(i < self.len && j < self.len, "index out of bounds");
+        let (i, j) = (self.physical(i), self.physical(j));
+        self.data.swap(i, j);
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get_raw(0)
+    }
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_raw_mut(0)
+    }
+    pub fn back(&self) -> Option<&T> {
+        self.get_raw(self.len.checked_sub(1)?)
+    }
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_raw_mut(self.len.checked_sub(1)?)
+    }
+
+    /// Returns the element back if the deque is full.
+    pub fn push_back(&mut self, v: T) -> Result<(), T> {
+        if self.len == CAP {
+            return Err(v);
+        }
+        let pos = self.physical(self.len);
+        let _ = self.data[pos].write(v);
+        self.len += 1;
+        Ok(())
+    }
+    /// Returns the element back if the deque is full.
+    pub fn push_front(&mut self, v: T) -> Result<(), T> {
+        if self.len == CAP {
+            return Err(v);
+        }
+        self.head = if self.head == 0 {
+            CAP - 1
+        } else {
+            self.head - 1
+        };
+        let _ = self.data[self.head].write(v);
+        self.len += 1;
+        Ok(())
+    }
+    pub fn push_back_get_idx(&mut self, v: T) -> Result<I, T>
+    where
+        I: Idx,
+    {
+        let idx = self.len_idx();
+        self.push_back(v)?;
+        Ok(idx)
+    }
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let v = unsafe { self.data[self.head].assume_init_read() };
+        self.head = self.physical(1);
+        self.len -= 1;
+        Some(v)
+    }
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.data[self.physical(self.len)].assume_init_read() })
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate_len(0);
+        self.head = 0;
+    }
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.truncate_len(end.into_usize());
+    }
+    pub fn truncate_len(&mut self, len: usize) {
+        while self.len > len {
+            drop(self.pop_back());
+        }
+    }
+
+    pub fn as_slices(&self) -> (&IndexSlice<I, T>, &IndexSlice<I, T>) {
+        let (a, b) = self.as_raw_slices();
+        (IndexSlice::from_slice(a), IndexSlice::from_slice(b))
+    }
+    pub fn as_mut_slices(&mut self) -> (&mut IndexSlice<I, T>, &mut IndexSlice<I, T>) {
+        let (a, b) = self.as_mut_raw_slices();
+        (IndexSlice::from_mut_slice(a), IndexSlice::from_mut_slice(b))
+    }
+    fn as_raw_slices(&self) -> (&[T], &[T]) {
+        let first_len = self.len.min(CAP - self.head);
+        let base = self.data.as_ptr().cast::<T>();
+        unsafe {
+            (
+                core::slice::from_raw_parts(base.add(self.head), first_len),
+                core::slice::from_raw_parts(base, self.len - first_len),
+            )
+        }
+    }
+    fn as_mut_raw_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first_len = self.len.min(CAP - self.head);
+        let base = self.data.as_mut_ptr().cast::<T>();
+        // the two ranges are disjoint: the second one ends before `head`
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(base.add(self.head), first_len),
+                core::slice::from_raw_parts_mut(base, self.len - first_len),
+            )
+        }
+    }
+    /// Rotates the storage so that all elements are in a single slice.
+    pub fn make_contiguous(&mut self) -> &mut IndexSlice<I, T> {
+        if self.head + self.len > CAP {
+            self.data.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (a, b) = self.as_raw_slices();
+        a.iter().chain(b)
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (a, b) = self.as_mut_raw_slices();
+        a.iter_mut().chain(b)
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, Iter<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.iter())
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, IterMut<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.iter_mut())
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, IntoIter<I, T, CAP>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self)
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+
+    /// Removes the elements in `range`, yielding them front to back.
+    ///
+    /// Elements that were not yielded are dropped when the [`Drain`] is.
+    /// Leaking the [`Drain`] leaks the elements in and after `range`.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, I, T, CAP>
+    where
+        R: IndexRangeBounds<I>,
+    {
+        let range = range.canonicalize(self.len);
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "drain range out of bounds"
+        );
+        let orig_len = self.len;
+        self.len = range.start;
+        Drain {
+            deque: self,
+            start: range.start,
+            front: range.start,
+            back: range.end,
+            end: range.end,
+            orig_len,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    /// Use with [`serde(serialize_with = "path")`](https://serde.rs/field-attrs.html#serialize_with)
+    /// to serialize as a map instead of an array.
+    pub fn serialize_as_map<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        I: Idx + Serialize,
+        T: Serialize,
+    {
+        serializer.collect_map(self.iter_enumerated())
+    }
+}
+
+impl<I, T, const CAP: usize> Drop for IndexArrayDeque<I, T, CAP> {
+    fn drop(&mut self) {
+        let (a, b) = self.as_mut_raw_slices();
+        unsafe {
+            core::ptr::drop_in_place(core::ptr::from_mut(a));
+            core::ptr::drop_in_place(core::ptr::from_mut(b));
+        }
+    }
+}
+
+impl<I, T: Clone, const CAP: usize> Clone for IndexArrayDeque<I, T, CAP> {
+    fn clone(&self) -> Self {
+        let mut res = Self::new();
+        for v in self {
+            let _ = res.push_back(v.clone());
+        }
+        res
+    }
+}
+
+impl<I, T: Debug, const CAP: usize> Debug for IndexArrayDeque<I, T, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<I, T, const CAP: usize> Default for IndexArrayDeque<I, T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> Extend<T> for IndexArrayDeque<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        for v in iter {
+            assert!(
+                self.push_back(v).is_ok(),
+                "IndexArrayDeque capacity of {CAP} exceeded"
+            );
+        }
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<'a, I, T: 'a + Copy, const CAP: usize> Extend<&'a T> for IndexArrayDeque<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = &'a T>>(&mut self, iter: It) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> FromIterator<T> for IndexArrayDeque<I, T, CAP> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<I, T, const CAP: usize> From<[T; CAP]> for IndexArrayDeque<I, T, CAP> {
+    fn from(value: [T; CAP]) -> Self {
+        Self::from_array(value)
+    }
+}
+
+impl<I, T, const CAP: usize> From<IndexArray<I, T, CAP>> for IndexArrayDeque<I, T, CAP> {
+    fn from(value: IndexArray<I, T, CAP>) -> Self {
+        Self::from_index_array(value)
+    }
+}
+
+impl<I, X, T, const CAP: usize> Index<X> for IndexArrayDeque<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    type Output = T;
+    #[inline]
+    fn index(&self, index: X) -> &Self::Output {
+        self.get(index)
+            .expect("IndexArrayDeque index out of bounds")
+    }
+}
+
+impl<I, X, T, const CAP: usize> IndexMut<X> for IndexArrayDeque<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    #[inline]
+    fn index_mut(&mut self, index: X) -> &mut Self::Output {
+        self.get_mut(index)
+            .expect("IndexArrayDeque index out of bounds")
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a IndexArrayDeque<I, T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a mut IndexArrayDeque<I, T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<I, T, const CAP: usize> IntoIterator for IndexArrayDeque<I, T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<I, T, CAP>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+impl<I, T, U, const CAP: usize, const CAP2: usize> PartialEq<IndexArrayDeque<I, U, CAP2>>
+    for IndexArrayDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &IndexArrayDeque<I, U, CAP2>) -> bool {
+        self.len == other.len && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<I, T, U, const CAP: usize> PartialEq<[U]> for IndexArrayDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.len == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<I, T, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for IndexArrayDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == *other.as_slice()
+    }
+}
+
+impl<I, T: Eq, const CAP: usize> Eq for IndexArrayDeque<I, T, CAP> {}
+
+impl<I, T: PartialOrd, const CAP: usize> PartialOrd for IndexArrayDeque<I, T, CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<I, T: Ord, const CAP: usize> Ord for IndexArrayDeque<I, T, CAP> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<I, T: Hash, const CAP: usize> Hash for IndexArrayDeque<I, T, CAP> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        self.iter().for_each(|v| v.hash(state));
+    }
+}
+
+// ===== IntoIter =====
+pub struct IntoIter<I, T, const CAP: usize> {
+    deque: IndexArrayDeque<I, T, CAP>,
+}
+
+impl<I, T, const CAP: usize> Iterator for IntoIter<I, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<I, T, const CAP: usize> DoubleEndedIterator for IntoIter<I, T, CAP> {
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<I, T, const CAP: usize> ExactSizeIterator for IntoIter<I, T, CAP> {}
+
+impl<I, T, const CAP: usize> FusedIterator for IntoIter<I, T, CAP> {}
+
+// ===== Drain =====
+pub struct Drain<'a, I, T, const CAP: usize> {
+    // `deque.len` is set to `start` while draining
+    deque: &'a mut IndexArrayDeque<I, T, CAP>,
+    start: usize,
+    // remaining, not yet yielded range
+    front: usize,
+    back: usize,
+    end: usize,
+    orig_len: usize,
+}
+
+impl<I, T, const CAP: usize> Drain<'_, I, T, CAP> {
+    fn take(&mut self, i: usize) -> T {
+        let pos = self.deque.physical(i);
+        unsafe { self.deque.data[pos].assume_init_read() }
+    }
+}
+
+impl<I, T, const CAP: usize> Iterator for Drain<'_, I, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.take(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<I, T, const CAP: usize> DoubleEndedIterator for Drain<'_, I, T, CAP> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.take(self.back))
+    }
+}
+
+impl<I, T, const CAP: usize> ExactSizeIterator for Drain<'_, I, T, CAP> {}
+
+impl<I, T, const CAP: usize> FusedIterator for Drain<'_, I, T, CAP> {}
+
+impl<I, T, const CAP: usize> Drop for Drain<'_, I, T, CAP> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+        let tail_len = self.orig_len - self.end;
+        // shift the tail down element by element so the copies never overlap
+        for i in 0..tail_len {
+            let src = self.deque.physical(self.end + i);
+            let dst = self.deque.physical(self.start + i);
+            unsafe {
+                let v = self.deque.data[src].assume_init_read();
+                let _ = self.deque.data[dst].write(v);
+            }
+        }
+        self.deque.len = self.start + tail_len;
+    }
+}
+
+// ===== serde =====
+#[cfg(feature = "serde")]
+impl<I, T, const CAP: usize> Serialize for IndexArrayDeque<I, T, CAP>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T, const CAP: usize> Deserialize<'de> for IndexArrayDeque<I, T, CAP>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct IndexArrayDequeVisitor<I, T, const CAP: usize>(PhantomData<fn(I) -> T>);
+
+        impl<'de, I, T, const CAP: usize> Visitor<'de> for IndexArrayDequeVisitor<I, T, CAP>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = IndexArrayDeque<I, T, CAP>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "an array with no more than {CAP} items")
+            }
+
+            fn visit_seq<SA>(self, mut seq: SA) -> Result<Self::Value, SA::Error>
+            where
+                SA: SeqAccess<'de>,
+            {
+                let mut values = IndexArrayDeque::new();
+
+                while let Some(value) = seq.next_element()? {
+                    if values.push_back(value).is_err() {
+                        return Err(SA::Error::invalid_length(CAP + 1, &self));
+                    }
+                }
+
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_seq(IndexArrayDequeVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexArrayDeque;
+
+    #[test]
+    fn wrap_around() {
+        let mut d = IndexArrayDeque::<u32, i32, 4>::from_array([1, 2, 3]);
+        assert_eq!(d.pop_front(), Some(1));
+        d.push_back(4).unwrap();
+        d.push_back(5).unwrap();
+        assert_eq!(d.push_front(0), Err(0));
+        let (a, b) = d.as_slices();
+        assert_eq!((a.as_slice(), b.as_slice()), (&[2, 3, 4][..], &[5][..]));
+        assert!(d.iter_enumerated().eq([(0, &2), (1, &3), (2, &4), (3, &5)]));
+        assert_eq!(d.make_contiguous().as_slice(), [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn drain_closes_gap() {
+        let mut d = IndexArrayDeque::<u32, i32, 5>::new();
+        d.extend([2, 3, 4]);
+        d.push_front(1).unwrap();
+        d.push_front(0).unwrap();
+        let mut drain = d.drain(1..3);
+        assert_eq!(drain.next_back(), Some(2));
+        drop(drain);
+        assert_eq!(d, [0, 3, 4]);
+        assert!(d.drain(..).eq([0, 3, 4]));
+        assert!(d.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "drain range out of bounds")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn drain_reversed_range() {
+        use alloc::string::String;
+
+        let mut d = IndexArrayDeque::<u32, String, 5>::new();
+        d.extend(["a", "b", "c", "d"].map(String::from));
+        drop(d.drain(3..1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let d = IndexArrayDeque::<u32, i32, 3>::from_array([1, 2, 3]);
+        let s = serde_json::to_string(&d).unwrap();
+        assert_eq!(s, "[1,2,3]");
+        assert_eq!(
+            serde_json::from_str::<IndexArrayDeque<u32, i32, 3>>(&s).unwrap(),
+            d
+        );
+        assert!(serde_json::from_str::<IndexArrayDeque<u32, i32, 2>>(&s).is_err());
+    }
+}
@@ -0,0 +1,938 @@
+//! A fixed capacity slab that needs no allocation.
+//!
+//! [`IndexArraySlab`] stores its entries inline in a `[_; CAP]` and threads
+//! the free list through the vacant entries, just like [`slab::Slab`] does
+//! for its `Vec`. Keys are stable until the element is removed.
+//!
+//! Since [`IndexArraySlab::new`] is a `const fn`, the slab can be used to
+//! initialize a `static`.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexArraySlab};
+//!
+//! #[derive(Idx)]
+//! struct TaskId(u8);
+//!
+//! let mut tasks = IndexArraySlab::<TaskId, &str, 2>::new();
+//! let a = tasks.try_insert("blink").unwrap();
+//! let b = tasks.try_insert("poll").unwrap();
+//! assert_eq!(tasks.try_insert("sleep"), Err("sleep"));
+//!
+//! tasks.remove(a);
+//! assert_eq!(tasks.try_insert("sleep"), Ok(a));
+//! assert_eq!(tasks[b], "poll");
+//! ```
+//!
+//! [`slab::Slab`]: https://docs.rs/slab/latest/slab/struct.Slab.html
+
+use core::{
+    fmt::{self, Debug},
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    ops,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Idx, idx::IdxCompat};
+
+#[derive(Clone)]
+enum Entry<T> {
+    // next entry of the free list
+    Vacant(usize),
+    Occupied(T),
+}
+
+pub struct IndexArraySlab<I, T, const CAP: usize> {
+    entries: [Entry<T>; CAP],
+    len: usize,
+    // entries at and above this index have never been occupied
+    used: usize,
+    // head of the free list, `used` if the list is empty
+    next: usize,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+/// The error type returned by [`IndexArraySlab::get_disjoint_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GetDisjointMutError {
+    /// An index provided was not associated with a value.
+    IndexVacant,
+
+    /// Two indices provided were overlapping.
+    OverlappingIndices,
+}
+
+impl fmt::Display for GetDisjointMutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            GetDisjointMutError::IndexVacant => "an index is vacant",
+            GetDisjointMutError::OverlappingIndices => "there were overlapping indices",
+        };
+        fmt::Display::fmt(msg, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetDisjointMutError {}
+
+impl<I, T, const CAP: usize> Clone for IndexArraySlab<I, T, CAP>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            len: self.len,
+            used: self.used,
+            next: self.next,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, T, const CAP: usize> Default for IndexArraySlab<I, T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T, const CAP: usize> IndexArraySlab<I, T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            entries: [const { Entry::Vacant(0) }; CAP],
+            len: 0,
+            used: 0,
+            next: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    pub fn clear(&mut self) {
+        for entry in &mut self.entries[..self.used] {
+            *entry = Entry::Vacant(0);
+        }
+        self.len = 0;
+        self.used = 0;
+        self.next = 0;
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.len)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    pub fn iter(&self) -> Iter<'_, I, T>
+    where
+        I: Idx,
+    {
+        Iter {
+            base: self.iter_enumerated(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, I, T>
+    where
+        I: Idx,
+    {
+        IterMut {
+            base: self.iter_enumerated_mut(),
+        }
+    }
+
+    fn entry(&self, key: usize) -> Option<&Entry<T>> {
+        self.entries[..self.used].get(key)
+    }
+
+    pub fn get(&self, key: I) -> Option<&T>
+    where
+        I: Idx,
+    {
+        match self.entry(key.into_usize()) {
+            Some(Entry::Occupied(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: I) -> Option<&mut T>
+    where
+        I: Idx,
+    {
+        match self.entries[..self.used].get_mut(key.into_usize()) {
+            Some(Entry::Occupied(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_disjoint_mut<const N: usize, X>(
+        &mut self,
+        indices: [X; N],
+    ) -> Result<[&mut T; N], GetDisjointMutError>
+    where
+        X: IdxCompat<I>,
+    {
+        let indices = indices.map(X::into_usize);
+        if !indices
+            .iter()
+            .all(|&i| matches!(self.entry(i), Some(Entry::Occupied(_))))
+        {
+            return Err(GetDisjointMutError::IndexVacant);
+        }
+        let entries = self
+            .entries
+            .get_disjoint_mut(indices)
+            .map_err(|_| GetDisjointMutError::OverlappingIndices)?;
+        Ok(entries.map(|e| match e {
+            Entry::Occupied(v) => v,
+            Entry::Vacant(_) => unreachable!(),
+        }))
+    }
+
+    /// ## Safety
+    /// The key must refer to an occupied entry.
+    pub unsafe fn get_unchecked(&self, key: I) -> &T
+    where
+        I: Idx,
+    {
+        match unsafe { self.entries.get_unchecked(key.into_usize_unchecked()) } {
+            Entry::Occupied(v) => v,
+            Entry::Vacant(_) => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+
+    /// ## Safety
+    /// The key must refer to an occupied entry.
+    pub unsafe fn get_unchecked_mut(&mut self, key: I) -> &mut T
+    where
+        I: Idx,
+    {
+        match unsafe { self.entries.get_unchecked_mut(key.into_usize_unchecked()) } {
+            Entry::Occupied(v) => v,
+            Entry::Vacant(_) => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+
+    /// Get the key of an element that is stored in this slab.
+    ///
+    /// # Panics
+    /// Panics if `present_element` does not point into this slab.
+    pub fn key_of(&self, present_element: &T) -> I
+    where
+        I: Idx,
+    {
+        let entry_size = size_of::<Entry<T>>();
+        let start = self.entries.as_ptr() as usize;
+        let ptr = core::ptr::from_ref(present_element) as usize;
+        assert!(
+            start <= ptr && ptr < start + self.used * entry_size,
+            "element is not stored in this slab"
+        );
+        I::from_usize((ptr - start) / entry_size)
+    }
+
+    /// Inserts `val`, returning it back if the slab is full.
+    pub fn try_insert(&mut self, val: T) -> Result<I, T>
+    where
+        I: Idx,
+    {
+        if self.len == CAP {
+            return Err(val);
+        }
+        let key = self.next;
+        if key == self.used {
+            self.used += 1;
+            self.next = self.used;
+        } else if let Entry::Vacant(next) = self.entries[key] {
+            self.next = next;
+        }
+        self.entries[key] = Entry::Occupied(val);
+        self.len += 1;
+        Ok(I::from_usize(key))
+    }
+
+    /// # Panics
+    /// Panics if the slab is full.
+    pub fn insert(&mut self, val: T) -> I
+    where
+        I: Idx,
+    {
+        let Ok(key) = self.try_insert(val) else {
+            panic!("IndexArraySlab capacity of {CAP} exceeded")
+        };
+        key
+    }
+
+    /// The key that the next insertion will use, or `None` if the slab is
+    /// full.
+    pub fn vacant_key(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        (self.len < CAP).then(|| I::from_usize(self.next))
+    }
+
+    pub fn vacant_entry(&mut self) -> Option<VacantEntry<'_, I, T, CAP>> {
+        if self.len == CAP {
+            return None;
+        }
+        Some(VacantEntry { slab: self })
+    }
+
+    pub fn try_remove(&mut self, key: I) -> Option<T>
+    where
+        I: Idx,
+    {
+        let key = key.into_usize();
+        if !matches!(self.entry(key), Some(Entry::Occupied(_))) {
+            return None;
+        }
+        let Entry::Occupied(val) =
+            core::mem::replace(&mut self.entries[key], Entry::Vacant(self.next))
+        else {
+            unreachable!()
+        };
+        self.next = key;
+        self.len -= 1;
+        Some(val)
+    }
+
+    /// # Panics
+    /// Panics if `key` is not occupied.
+    pub fn remove(&mut self, key: I) -> T
+    where
+        I: Idx,
+    {
+        self.try_remove(key).expect("invalid key")
+    }
+
+    pub fn contains(&self, key: I) -> bool
+    where
+        I: Idx,
+    {
+        matches!(self.entry(key.into_usize()), Some(Entry::Occupied(_)))
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(I, &mut T) -> bool,
+        I: Idx,
+    {
+        for key in 0..self.used {
+            let keep = match &mut self.entries[key] {
+                Entry::Occupied(v) => f(I::from_usize(key), v),
+                Entry::Vacant(_) => true,
+            };
+            if !keep {
+                let _ = self.try_remove(I::from_usize(key));
+            }
+        }
+    }
+
+    /// Removes all elements, yielding them in key order.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        let used = self.used;
+        self.len = 0;
+        self.used = 0;
+        self.next = 0;
+        Drain {
+            base: self.entries[..used].iter_mut(),
+        }
+    }
+
+    pub fn iter_enumerated(&self) -> IterEnumerated<'_, I, T> {
+        IterEnumerated {
+            base: self.entries[..self.used].iter().enumerate(),
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn iter_enumerated_mut(&mut self) -> IterEnumeratedMut<'_, I, T> {
+        IterEnumeratedMut {
+            base: self.entries[..self.used].iter_mut().enumerate(),
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn indices(&self) -> impl Iterator<Item = I>
+    where
+        I: Idx,
+    {
+        self.iter_enumerated().map(|(key, _value)| key)
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(I, T) -> U) -> IndexArraySlab<I, U, CAP>
+    where
+        I: Idx,
+    {
+        let mut key = 0;
+        let entries = self.entries.map(|entry| {
+            let res = match entry {
+                Entry::Occupied(value) if key < self.used => {
+                    Entry::Occupied(f(I::from_usize(key), value))
+                }
+                Entry::Occupied(_) => Entry::Vacant(0),
+                Entry::Vacant(next) => Entry::Vacant(next),
+            };
+            key += 1;
+            res
+        });
+        IndexArraySlab {
+            entries,
+            len: self.len,
+            used: self.used,
+            next: self.next,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Builds a slab from key value pairs, returning the first key that is
+    /// out of bounds for the capacity.
+    fn try_from_pairs(pairs: impl IntoIterator<Item = (usize, T)>) -> Result<Self, usize> {
+        let mut res = Self::new();
+        for (key, value) in pairs {
+            if key >= CAP {
+                return Err(key);
+            }
+            if !matches!(res.entries[key], Entry::Occupied(_)) {
+                res.len += 1;
+            }
+            res.entries[key] = Entry::Occupied(value);
+            res.used = res.used.max(key + 1);
+        }
+        // rebuild the free list so that lower keys are reused first
+        res.next = res.used;
+        for key in (0..res.used).rev() {
+            if let Entry::Vacant(next) = &mut res.entries[key] {
+                *next = res.next;
+                res.next = key;
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<I, X, T, const CAP: usize> ops::Index<X> for IndexArraySlab<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    type Output = T;
+
+    fn index(&self, key: X) -> &T {
+        match self.entry(key.into_usize()) {
+            Some(Entry::Occupied(v)) => v,
+            _ => panic!("invalid key"),
+        }
+    }
+}
+
+impl<I, X, T, const CAP: usize> ops::IndexMut<X> for IndexArraySlab<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    fn index_mut(&mut self, key: X) -> &mut T {
+        match self.entries[..self.used].get_mut(key.into_usize()) {
+            Some(Entry::Occupied(v)) => v,
+            _ => panic!("invalid key"),
+        }
+    }
+}
+
+impl<I, T, const CAP: usize> IntoIterator for IndexArraySlab<I, T, CAP>
+where
+    I: Idx,
+{
+    type Item = (I, T);
+    type IntoIter = IntoIter<I, T, CAP>;
+
+    fn into_iter(self) -> IntoIter<I, T, CAP> {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a IndexArraySlab<I, T, CAP>
+where
+    I: Idx,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, I, T>;
+
+    fn into_iter(self) -> Iter<'a, I, T> {
+        self.iter()
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a mut IndexArraySlab<I, T, CAP>
+where
+    I: Idx,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, I, T>;
+
+    fn into_iter(self) -> IterMut<'a, I, T> {
+        self.iter_mut()
+    }
+}
+
+/// # Panics
+/// Panics if a key is out of bounds for the capacity.
+impl<I, T, const CAP: usize> FromIterator<(I, T)> for IndexArraySlab<I, T, CAP>
+where
+    I: Idx,
+{
+    fn from_iter<It>(iterable: It) -> Self
+    where
+        It: IntoIterator<Item = (I, T)>,
+    {
+        let pairs = iterable
+            .into_iter()
+            .map(|(key, val)| (key.into_usize(), val));
+        match Self::try_from_pairs(pairs) {
+            Ok(slab) => slab,
+            Err(key) => panic!("key {key} exceeds IndexArraySlab capacity of {CAP}"),
+        }
+    }
+}
+
+impl<I, T, const CAP: usize> fmt::Debug for IndexArraySlab<I, T, CAP>
+where
+    I: Idx + Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if fmt.alternate() {
+            fmt.debug_map().entries(self.iter_enumerated()).finish()
+        } else {
+            fmt.debug_struct("IndexArraySlab")
+                .field("len", &self.len)
+                .field("cap", &CAP)
+                .finish_non_exhaustive()
+        }
+    }
+}
+
+// ===== VacantEntry =====
+pub struct VacantEntry<'a, I, T, const CAP: usize> {
+    slab: &'a mut IndexArraySlab<I, T, CAP>,
+}
+
+impl<'a, I, T, const CAP: usize> VacantEntry<'a, I, T, CAP> {
+    pub fn insert(self, val: T) -> &'a mut T
+    where
+        I: Idx,
+    {
+        let Ok(key) = self.slab.try_insert(val) else {
+            unreachable!("vacant entries are only handed out if there is space")
+        };
+        match &mut self.slab.entries[key.into_usize()] {
+            Entry::Occupied(v) => v,
+            Entry::Vacant(_) => unreachable!(),
+        }
+    }
+
+    pub fn key(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.slab.next)
+    }
+}
+
+// ===== IntoIter =====
+pub struct IntoIter<I, T, const CAP: usize> {
+    base: Enumerate<core::array::IntoIter<Entry<T>, CAP>>,
+    len: usize,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T, const CAP: usize> IntoIter<I, T, CAP> {
+    fn new(slab: IndexArraySlab<I, T, CAP>) -> Self {
+        let mut base = slab.entries.into_iter().enumerate();
+        // drop entries that are stale after a leaked `Drain`
+        if slab.used < CAP {
+            let _ = base.nth_back(CAP - slab.used - 1);
+        }
+        Self {
+            base,
+            len: slab.len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, T, const CAP: usize> Iterator for IntoIter<I, T, CAP>
+where
+    I: Idx,
+{
+    type Item = (I, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (key, entry) in &mut self.base {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I, T, const CAP: usize> DoubleEndedIterator for IntoIter<I, T, CAP>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some((key, entry)) = self.base.next_back() {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+}
+
+impl<I, T, const CAP: usize> ExactSizeIterator for IntoIter<I, T, CAP> where I: Idx {}
+
+impl<I, T, const CAP: usize> FusedIterator for IntoIter<I, T, CAP> where I: Idx {}
+
+// ===== Drain =====
+pub struct Drain<'a, T> {
+    base: core::slice::IterMut<'a, Entry<T>>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for entry in &mut self.base {
+            if let Entry::Occupied(value) = core::mem::replace(entry, Entry::Vacant(0)) {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        while let Some(entry) = self.base.next_back() {
+            if let Entry::Occupied(value) = core::mem::replace(entry, Entry::Vacant(0)) {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+// ===== Iter =====
+pub struct Iter<'a, I, T> {
+    base: IterEnumerated<'a, I, T>,
+}
+
+impl<'a, I, T> Iterator for Iter<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.base.next().map(|(_key, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<I, T> DoubleEndedIterator for Iter<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back().map(|(_key, value)| value)
+    }
+}
+
+impl<I, T> ExactSizeIterator for Iter<'_, I, T> where I: Idx {}
+
+impl<I, T> FusedIterator for Iter<'_, I, T> where I: Idx {}
+
+// ===== IterMut =====
+pub struct IterMut<'a, I, T> {
+    base: IterEnumeratedMut<'a, I, T>,
+}
+
+impl<'a, I, T> Iterator for IterMut<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.base.next().map(|(_key, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<I, T> DoubleEndedIterator for IterMut<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back().map(|(_key, value)| value)
+    }
+}
+
+impl<I, T> ExactSizeIterator for IterMut<'_, I, T> where I: Idx {}
+
+impl<I, T> FusedIterator for IterMut<'_, I, T> where I: Idx {}
+
+// ===== IterEnumerated =====
+pub struct IterEnumerated<'a, I, T> {
+    base: Enumerate<core::slice::Iter<'a, Entry<T>>>,
+    len: usize,
+    _phantom: PhantomData<fn(I) -> &'a T>,
+}
+
+impl<'a, I, T> Iterator for IterEnumerated<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = (I, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (key, entry) in &mut self.base {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I, T> DoubleEndedIterator for IterEnumerated<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some((key, entry)) = self.base.next_back() {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+}
+
+impl<I, T> ExactSizeIterator for IterEnumerated<'_, I, T> where I: Idx {}
+
+impl<I, T> FusedIterator for IterEnumerated<'_, I, T> where I: Idx {}
+
+// ===== IterEnumeratedMut =====
+pub struct IterEnumeratedMut<'a, I, T> {
+    base: Enumerate<core::slice::IterMut<'a, Entry<T>>>,
+    len: usize,
+    _phantom: PhantomData<fn(I) -> &'a mut T>,
+}
+
+impl<'a, I, T> Iterator for IterEnumeratedMut<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = (I, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (key, entry) in &mut self.base {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I, T> DoubleEndedIterator for IterEnumeratedMut<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some((key, entry)) = self.base.next_back() {
+            if let Entry::Occupied(value) = entry {
+                self.len -= 1;
+                return Some((I::from_usize(key), value));
+            }
+        }
+        None
+    }
+}
+
+impl<I, T> ExactSizeIterator for IterEnumeratedMut<'_, I, T> where I: Idx {}
+
+impl<I, T> FusedIterator for IterEnumeratedMut<'_, I, T> where I: Idx {}
+
+// ===== serde =====
+#[cfg(feature = "serde")]
+impl<I, T, const CAP: usize> Serialize for IndexArraySlab<I, T, CAP>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(self.entries[..self.used].iter().enumerate().filter_map(
+            |(key, entry)| match entry {
+                Entry::Occupied(value) => Some((key, value)),
+                Entry::Vacant(_) => None,
+            },
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T, const CAP: usize> Deserialize<'de> for IndexArraySlab<I, T, CAP>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{Error, MapAccess, Visitor};
+
+        struct SlabVisitor<I, T, const CAP: usize>(PhantomData<fn(I) -> T>);
+
+        impl<'de, I, T, const CAP: usize> Visitor<'de> for SlabVisitor<I, T, CAP>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = IndexArraySlab<I, T, CAP>;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a map with keys less than {CAP}")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut err = None;
+                let pairs = core::iter::from_fn(|| match map.next_entry() {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        err = Some(e);
+                        None
+                    }
+                });
+                let res = IndexArraySlab::try_from_pairs(pairs);
+                if let Some(e) = err {
+                    return Err(e);
+                }
+                res.map_err(|key| {
+                    A::Error::custom(format_args!("key {key} exceeds capacity {CAP}"))
+                })
+            }
+        }
+
+        deserializer.deserialize_map(SlabVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GetDisjointMutError, IndexArraySlab};
+
+    static EMPTY: IndexArraySlab<u32, u32, 4> = IndexArraySlab::new();
+
+    #[test]
+    fn free_list_reuses_keys() {
+        assert!(EMPTY.is_empty());
+        let mut slab = IndexArraySlab::<u32, char, 4>::new();
+        for c in ['a', 'b', 'c', 'd'] {
+            let _ = slab.insert(c);
+        }
+        assert_eq!(slab.try_insert('e'), Err('e'));
+        assert_eq!(slab.remove(1), 'b');
+        assert_eq!(slab.remove(3), 'd');
+        assert_eq!(slab.vacant_key(), Some(3));
+        assert_eq!(slab.insert('x'), 3);
+        assert_eq!(slab.insert('y'), 1);
+        assert!(
+            slab.iter_enumerated()
+                .eq([(0, &'a'), (1, &'y'), (2, &'c'), (3, &'x')])
+        );
+
+        let [a, c] = slab.get_disjoint_mut([0u32, 2]).unwrap();
+        core::mem::swap(a, c);
+        assert_eq!(slab[0], 'c');
+        assert_eq!(
+            slab.get_disjoint_mut([1u32, 1]).err(),
+            Some(GetDisjointMutError::OverlappingIndices)
+        );
+
+        slab.retain(|_, c| *c != 'y');
+        assert!(slab.drain().eq(['c', 'a', 'x']));
+        assert!(slab.is_empty());
+        assert_eq!(slab.insert('z'), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let slab: IndexArraySlab<u32, i32, 4> = [(0, 1), (2, 3)].into_iter().collect();
+        let s = serde_json::to_string(&slab).unwrap();
+        assert_eq!(s, r#"{"0":1,"2":3}"#);
+        let mut slab2: IndexArraySlab<u32, i32, 4> = serde_json::from_str(&s).unwrap();
+        assert_eq!(slab2.insert(5), 1);
+        assert_eq!(slab2.insert(6), 3);
+        assert!(serde_json::from_str::<IndexArraySlab<u32, i32, 2>>(&s).is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! A dense side table that is implicitly filled with default values.
+//!
+//! [`IndexDefaultVec`] behaves as if it had infinite length: reading an index
+//! past the end yields a shared default value and writing to it grows the
+//! underlying [`IndexVec`]. Trailing default values are not considered
+//! significant, so they are ignored by comparisons, hashing and serialization.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexDefaultVec};
+//!
+//! #[derive(Idx)]
+//! struct NodeId(u32);
+//!
+//! let mut depth = IndexDefaultVec::<NodeId, u32>::new();
+//! assert_eq!(depth[NodeId(10)], 0);
+//!
+//! depth[NodeId(3)] = 7; // grows the vec to a length of 4
+//! assert_eq!(depth.len(), 4);
+//!
+//! depth[NodeId(3)] = 0;
+//! depth.trim();
+//! assert_eq!(depth.len(), 0);
+//! ```
+
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Index, IndexMut},
+};
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Idx, IndexSlice, IndexVec, index_enumerate::IndexEnumerate};
+
+pub struct IndexDefaultVec<I, T> {
+    data: IndexVec<I, T>,
+    // returned by reference for reads past the end
+    default: T,
+}
+
+impl<I, T> IndexDefaultVec<I, T> {
+    pub fn new() -> Self
+    where
+        T: Default,
+    {
+        Self::from_index_vec(IndexVec::new())
+    }
+
+    pub fn with_capacity(cap: usize) -> Self
+    where
+        T: Default,
+    {
+        Self::from_index_vec(IndexVec::with_capacity(cap))
+    }
+
+    pub fn from_index_vec(data: IndexVec<I, T>) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            data,
+            default: T::default(),
+        }
+    }
+
+    /// The length of the stored prefix, which may include trailing defaults.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        self.data.len_idx()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns the value at `idx`, or the default value if `idx` is past the end.
+    pub fn get(&self, idx: I) -> &T
+    where
+        I: Idx,
+    {
+        self.data.get(idx).unwrap_or(&self.default)
+    }
+
+    /// Returns the value at `idx`, growing the vec if necessary.
+    pub fn get_mut(&mut self, idx: I) -> &mut T
+    where
+        I: Idx,
+        T: Default,
+    {
+        self.data.index_grow_mut(idx)
+    }
+
+    /// Sets the value at `idx`, growing the vec if necessary.
+    /// Returns the previous value.
+    pub fn set(&mut self, idx: I, value: T) -> T
+    where
+        I: Idx,
+        T: Default,
+    {
+        core::mem::replace(self.data.index_grow_mut(idx), value)
+    }
+
+    /// Resets the value at `idx` to the default value without growing the vec.
+    /// Returns the previous value.
+    pub fn take(&mut self, idx: I) -> T
+    where
+        I: Idx,
+        T: Default,
+    {
+        match self.data.get_mut(idx) {
+            Some(v) => core::mem::take(v),
+            None => T::default(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// The length of the prefix that contains non default values.
+    pub fn significant_len(&self) -> usize
+    where
+        T: PartialEq,
+    {
+        self.significant_prefix().len()
+    }
+
+    /// The stored prefix without trailing default values.
+    pub fn significant_prefix(&self) -> &IndexSlice<I, T>
+    where
+        T: PartialEq,
+    {
+        let len = self
+            .data
+            .iter()
+            .rposition(|v| *v != self.default)
+            .map_or(0, |i| i + 1);
+        IndexSlice::from_slice(&self.data.as_slice()[..len])
+    }
+
+    /// Removes trailing default values from the stored prefix.
+    pub fn trim(&mut self)
+    where
+        T: PartialEq,
+    {
+        let len = self.significant_len();
+        self.data.as_mut_vec().truncate(len);
+    }
+
+    pub fn as_index_slice(&self) -> &IndexSlice<I, T> {
+        self.data.as_index_slice()
+    }
+
+    pub fn as_mut_index_slice(&mut self) -> &mut IndexSlice<I, T> {
+        self.data.as_mut_index_slice()
+    }
+
+    pub fn as_index_vec(&self) -> &IndexVec<I, T> {
+        &self.data
+    }
+
+    /// Direct access to the underlying vec. Resizing it is fine, as indices
+    /// past the end are implicitly default.
+    pub fn as_mut_index_vec(&mut self) -> &mut IndexVec<I, T> {
+        &mut self.data
+    }
+
+    pub fn into_index_vec(self) -> IndexVec<I, T> {
+        self.data
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// Iterates over the stored prefix.
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, core::slice::Iter<'_, T>>
+    where
+        I: Idx,
+    {
+        self.data.iter_enumerated()
+    }
+
+    /// Iterates over the stored prefix.
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, core::slice::IterMut<'_, T>>
+    where
+        I: Idx,
+    {
+        self.data.iter_enumerated_mut()
+    }
+}
+
+impl<I, T: Default> Default for IndexDefaultVec<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T: Clone> Clone for IndexDefaultVec<I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+impl<I, T: Debug> Debug for IndexDefaultVec<I, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I, T> Index<I> for IndexDefaultVec<I, T>
+where
+    I: Idx,
+{
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        self.get(index)
+    }
+}
+
+impl<I, T> IndexMut<I> for IndexDefaultVec<I, T>
+where
+    I: Idx,
+    T: Default,
+{
+    fn index_mut(&mut self, index: I) -> &mut T {
+        self.data.index_grow_mut(index)
+    }
+}
+
+impl<I, T: PartialEq> PartialEq for IndexDefaultVec<I, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.significant_prefix() == other.significant_prefix()
+    }
+}
+
+impl<I, T: Eq> Eq for IndexDefaultVec<I, T> {}
+
+impl<I, T: Hash + PartialEq> Hash for IndexDefaultVec<I, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.significant_prefix().as_slice().hash(state);
+    }
+}
+
+impl<I, T: Default> From<IndexVec<I, T>> for IndexDefaultVec<I, T> {
+    fn from(value: IndexVec<I, T>) -> Self {
+        Self::from_index_vec(value)
+    }
+}
+
+impl<I, T: Default> From<Vec<T>> for IndexDefaultVec<I, T> {
+    fn from(value: Vec<T>) -> Self {
+        Self::from_index_vec(IndexVec::from(value))
+    }
+}
+
+impl<I, T> From<IndexDefaultVec<I, T>> for IndexVec<I, T> {
+    fn from(value: IndexDefaultVec<I, T>) -> Self {
+        value.data
+    }
+}
+
+impl<I, T: Default> FromIterator<T> for IndexDefaultVec<I, T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::from_index_vec(IndexVec::from_iter(iter))
+    }
+}
+
+impl<I, T> Extend<T> for IndexDefaultVec<I, T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a IndexDefaultVec<I, T> {
+    type Item = &'a T;
+
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a mut IndexDefaultVec<I, T> {
+    type Item = &'a mut T;
+
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I, T> Serialize for IndexDefaultVec<I, T>
+where
+    T: Serialize + PartialEq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.significant_prefix().as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T> Deserialize<'de> for IndexDefaultVec<I, T>
+where
+    T: Deserialize<'de> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(Vec::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexDefaultVec;
+
+    #[test]
+    fn trailing_defaults_are_insignificant() {
+        let mut a = IndexDefaultVec::<u32, i32>::new();
+        let mut b = IndexDefaultVec::<u32, i32>::new();
+        a[2] = 1;
+        b[2] = 1;
+        b[10] = 0;
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 11);
+        assert_eq!(b[100], 0);
+        assert_eq!(a, b);
+        b.trim();
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.take(2), 1);
+        b.trim();
+        assert!(b.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_significant_prefix() {
+        let mut v = IndexDefaultVec::<u32, i32>::new();
+        v[1] = 1;
+        v[5] = 0;
+        assert_eq!(serde_json::to_string(&v).unwrap(), "[0,1]");
+        assert_eq!(
+            serde_json::from_str::<IndexDefaultVec<u32, i32>>("[0,1,0]").unwrap(),
+            v
+        );
+    }
+}
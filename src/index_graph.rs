@@ -0,0 +1,570 @@
+//! A mutable graph with typed node and edge ids.
+//!
+//! [`IndexGraph<N, E, NW, EW>`] stores node weights of type `NW` indexed by
+//! `N` and edge weights of type `EW` indexed by `E`. The adjacency of every
+//! node is kept as intrusive linked lists threaded through the edges, so
+//! adding edges is O(1) and no per node allocations are needed.
+//!
+//! Removal uses swap-remove semantics: removing a node or an edge moves the
+//! last node or edge into the freed id, keeping all ids dense.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexGraph};
+//!
+//! #[derive(Idx)]
+//! struct NodeId(u32);
+//!
+//! #[derive(Idx)]
+//! struct EdgeId(u32);
+//!
+//! let mut graph = IndexGraph::<NodeId, EdgeId, &str, i32>::new();
+//! let a = graph.add_node("a");
+//! let b = graph.add_node("b");
+//! let c = graph.add_node("c");
+//! let ab = graph.add_edge(a, b, 3);
+//! let ac = graph.add_edge(a, c, 5);
+//!
+//! assert_eq!(graph.edge_endpoints(ab), (a, b));
+//! assert_eq!(graph.outgoing(a).count(), 2);
+//! assert!(graph.incoming(c).eq([(ac, a)]));
+//!
+//! // `ac` was the last edge, so no other edge was moved
+//! assert_eq!(graph.remove_edge(ac), (5, None));
+//! assert_eq!(graph.edge_weights().iter().sum::<i32>(), 3);
+//! ```
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Idx, IndexRange, IndexSlice, IndexVec};
+
+/// Terminates the adjacency lists.
+const NONE: usize = usize::MAX;
+
+/// Adjacency list heads of a node.
+/// Index 0 is the list of outgoing edges, index 1 the list of incoming edges.
+#[derive(Clone, Copy)]
+struct NodeLinks {
+    first: [usize; 2],
+}
+
+/// Index 0 refers to the source of the edge, index 1 to the target.
+#[derive(Clone, Copy)]
+struct EdgeLinks {
+    endpoints: [usize; 2],
+    next: [usize; 2],
+}
+
+pub struct IndexGraph<N, E, NW, EW> {
+    node_weights: IndexVec<N, NW>,
+    edge_weights: IndexVec<E, EW>,
+    nodes: Vec<NodeLinks>,
+    edges: Vec<EdgeLinks>,
+    directed: bool,
+}
+
+impl<N, E, NW, EW> IndexGraph<N, E, NW, EW> {
+    /// Creates an empty directed graph.
+    pub const fn new() -> Self {
+        Self::new_with_mode(true)
+    }
+
+    /// Creates an empty undirected graph. In undirected mode
+    /// [`outgoing`](Self::outgoing) and [`incoming`](Self::incoming) both
+    /// yield all edges incident to a node.
+    pub const fn new_undirected() -> Self {
+        Self::new_with_mode(false)
+    }
+
+    const fn new_with_mode(directed: bool) -> Self {
+        Self {
+            node_weights: IndexVec::new(),
+            edge_weights: IndexVec::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            directed,
+        }
+    }
+
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        let mut res = Self::new();
+        res.reserve(nodes, edges);
+        res
+    }
+
+    pub fn reserve(&mut self, nodes: usize, edges: usize) {
+        self.node_weights.reserve(nodes);
+        self.nodes.reserve(nodes);
+        self.edge_weights.reserve(edges);
+        self.edges.reserve(edges);
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.node_weights.clear();
+        self.edge_weights.clear();
+        self.nodes.clear();
+        self.edges.clear();
+    }
+
+    pub fn clear_edges(&mut self) {
+        self.edge_weights.clear();
+        self.edges.clear();
+        for node in &mut self.nodes {
+            node.first = [NONE; 2];
+        }
+    }
+
+    pub fn nodes(&self) -> IndexRange<N>
+    where
+        N: Idx,
+    {
+        self.node_weights.indices()
+    }
+
+    pub fn edges(&self) -> IndexRange<E>
+    where
+        E: Idx,
+    {
+        self.edge_weights.indices()
+    }
+
+    pub fn node_weights(&self) -> &IndexSlice<N, NW> {
+        &self.node_weights
+    }
+
+    pub fn node_weights_mut(&mut self) -> &mut IndexSlice<N, NW> {
+        &mut self.node_weights
+    }
+
+    pub fn edge_weights(&self) -> &IndexSlice<E, EW> {
+        &self.edge_weights
+    }
+
+    pub fn edge_weights_mut(&mut self) -> &mut IndexSlice<E, EW> {
+        &mut self.edge_weights
+    }
+
+    pub fn node_weight(&self, node: N) -> Option<&NW>
+    where
+        N: Idx,
+    {
+        self.node_weights.get(node)
+    }
+
+    pub fn node_weight_mut(&mut self, node: N) -> Option<&mut NW>
+    where
+        N: Idx,
+    {
+        self.node_weights.get_mut(node)
+    }
+
+    pub fn edge_weight(&self, edge: E) -> Option<&EW>
+    where
+        E: Idx,
+    {
+        self.edge_weights.get(edge)
+    }
+
+    pub fn edge_weight_mut(&mut self, edge: E) -> Option<&mut EW>
+    where
+        E: Idx,
+    {
+        self.edge_weights.get_mut(edge)
+    }
+
+    pub fn add_node(&mut self, weight: NW) -> N
+    where
+        N: Idx,
+    {
+        self.nodes.push(NodeLinks { first: [NONE; 2] });
+        self.node_weights.push_get_idx(weight)
+    }
+
+    /// Adds an edge from `from` to `to`. Parallel edges and self loops are
+    /// allowed.
+    pub fn add_edge(&mut self, from: N, to: N, weight: EW) -> E
+    where
+        N: Idx,
+        E: Idx,
+    {
+        let endpoints = [from.into_usize(), to.into_usize()];
+        assert!(
+            endpoints.iter().all(|&n| n < self.nodes.len()),
+            "edge endpoint out of bounds: the node count is {}",
+            self.nodes.len()
+        );
+        let edge = self.edges.len();
+        let next = [
+            self.nodes[endpoints[0]].first[0],
+            self.nodes[endpoints[1]].first[1],
+        ];
+        self.nodes[endpoints[0]].first[0] = edge;
+        self.nodes[endpoints[1]].first[1] = edge;
+        self.edges.push(EdgeLinks { endpoints, next });
+        self.edge_weights.push_get_idx(weight)
+    }
+
+    /// Returns the source and the target of `edge`.
+    pub fn edge_endpoints(&self, edge: E) -> (N, N)
+    where
+        N: Idx,
+        E: Idx,
+    {
+        let [from, to] = self.edges[edge.into_usize()].endpoints;
+        (N::from_usize(from), N::from_usize(to))
+    }
+
+    /// Returns any edge from `from` to `to`. In undirected mode, edges from
+    /// `to` to `from` are also considered.
+    pub fn find_edge(&self, from: N, to: N) -> Option<E>
+    where
+        N: Idx + PartialEq,
+        E: Idx,
+    {
+        self.outgoing(from)
+            .find(|&(_, n)| n == to)
+            .map(|(edge, _)| edge)
+    }
+
+    fn adjacent(&self, node: N, lists: [bool; 2]) -> Adjacent<'_, N, E>
+    where
+        N: Idx,
+    {
+        let node = node.into_usize();
+        let first = self.nodes[node].first;
+        Adjacent {
+            edges: &self.edges,
+            next: [
+                if lists[0] { first[0] } else { NONE },
+                if lists[1] { first[1] } else { NONE },
+            ],
+            skip_self_loops: lists[0] && lists[1],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the edges leaving `node`, together with their targets.
+    /// Edges are yielded in reverse insertion order.
+    pub fn outgoing(&self, node: N) -> Adjacent<'_, N, E>
+    where
+        N: Idx,
+    {
+        self.adjacent(node, [true, !self.directed])
+    }
+
+    /// Iterates over the edges entering `node`, together with their sources.
+    /// Edges are yielded in reverse insertion order.
+    pub fn incoming(&self, node: N) -> Adjacent<'_, N, E>
+    where
+        N: Idx,
+    {
+        self.adjacent(node, [!self.directed, true])
+    }
+
+    /// Replaces the link to `from` in the adjacency list `dir` of `node`
+    /// with `to`.
+    fn relink(&mut self, node: usize, dir: usize, from: usize, to: usize) {
+        if self.nodes[node].first[dir] == from {
+            self.nodes[node].first[dir] = to;
+            return;
+        }
+        let mut edge = self.nodes[node].first[dir];
+        while self.edges[edge].next[dir] != from {
+            edge = self.edges[edge].next[dir];
+        }
+        self.edges[edge].next[dir] = to;
+    }
+
+    /// Removes `edge` and returns its weight.
+    ///
+    /// The last edge is moved into the id of the removed edge. Its previous
+    /// id is returned, or `None` if the removed edge was the last one.
+    pub fn remove_edge(&mut self, edge: E) -> (EW, Option<E>)
+    where
+        E: Idx,
+    {
+        let edge = edge.into_usize();
+        let links = self.edges[edge];
+        for dir in 0..2 {
+            self.relink(links.endpoints[dir], dir, edge, links.next[dir]);
+        }
+        let last = self.edges.len() - 1;
+        let moved = if edge == last {
+            None
+        } else {
+            let moved = self.edges[last];
+            for dir in 0..2 {
+                self.relink(moved.endpoints[dir], dir, last, edge);
+            }
+            Some(E::from_usize(last))
+        };
+        let _ = self.edges.swap_remove(edge);
+        let weight = self.edge_weights.swap_remove(E::from_usize(edge));
+        (weight, moved)
+    }
+
+    /// Removes `node` and all edges incident to it, and returns its weight.
+    ///
+    /// The last node is moved into the id of the removed node. Its previous
+    /// id is returned, or `None` if the removed node was the last one.
+    /// Removing the incident edges moves other edges as described in
+    /// [`remove_edge`](Self::remove_edge). Remove them individually
+    /// beforehand if their ids need to be tracked.
+    pub fn remove_node(&mut self, node: N) -> (NW, Option<N>)
+    where
+        N: Idx,
+        E: Idx,
+    {
+        let node = node.into_usize();
+        for dir in 0..2 {
+            while self.nodes[node].first[dir] != NONE {
+                let _ = self.remove_edge(E::from_usize(self.nodes[node].first[dir]));
+            }
+        }
+        let last = self.nodes.len() - 1;
+        let moved = if node == last {
+            None
+        } else {
+            for dir in 0..2 {
+                let mut edge = self.nodes[last].first[dir];
+                while edge != NONE {
+                    self.edges[edge].endpoints[dir] = node;
+                    edge = self.edges[edge].next[dir];
+                }
+            }
+            Some(N::from_usize(last))
+        };
+        let _ = self.nodes.swap_remove(node);
+        let weight = self.node_weights.swap_remove(N::from_usize(node));
+        (weight, moved)
+    }
+}
+
+// ===== Adjacent =====
+
+/// Iterator over `(E, N)` pairs of the edges adjacent to a node and the
+/// nodes on their other end. Created by [`IndexGraph::outgoing`] and
+/// [`IndexGraph::incoming`].
+pub struct Adjacent<'a, N, E> {
+    edges: &'a [EdgeLinks],
+    next: [usize; 2],
+    // self loops appear in both lists, so in undirected mode we skip them
+    // in the incoming list
+    skip_self_loops: bool,
+    _phantom: PhantomData<fn() -> (N, E)>,
+}
+
+impl<N, E> Clone for Adjacent<'_, N, E> {
+    fn clone(&self) -> Self {
+        Self {
+            edges: self.edges,
+            next: self.next,
+            skip_self_loops: self.skip_self_loops,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<N: Idx, E: Idx> Iterator for Adjacent<'_, N, E> {
+    type Item = (E, N);
+
+    fn next(&mut self) -> Option<(E, N)> {
+        if self.next[0] != NONE {
+            let edge = self.next[0];
+            let links = &self.edges[edge];
+            self.next[0] = links.next[0];
+            return Some((E::from_usize(edge), N::from_usize(links.endpoints[1])));
+        }
+        while self.next[1] != NONE {
+            let edge = self.next[1];
+            let links = &self.edges[edge];
+            self.next[1] = links.next[1];
+            if self.skip_self_loops && links.endpoints[0] == links.endpoints[1] {
+                continue;
+            }
+            return Some((E::from_usize(edge), N::from_usize(links.endpoints[0])));
+        }
+        None
+    }
+}
+
+impl<N: Idx, E: Idx> core::iter::FusedIterator for Adjacent<'_, N, E> {}
+
+impl<N, E, NW, EW> Default for IndexGraph<N, E, NW, EW> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, E, NW: Clone, EW: Clone> Clone for IndexGraph<N, E, NW, EW> {
+    fn clone(&self) -> Self {
+        Self {
+            node_weights: self.node_weights.clone(),
+            edge_weights: self.edge_weights.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            directed: self.directed,
+        }
+    }
+}
+
+impl<N, E, NW: Debug, EW: Debug> Debug for IndexGraph<N, E, NW, EW> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct EdgeList<'a, EW>(&'a [EdgeLinks], &'a [EW]);
+        impl<EW: Debug> Debug for EdgeList<'_, EW> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list()
+                    .entries(
+                        self.0.iter().zip(self.1).map(|(links, weight)| {
+                            (links.endpoints[0], links.endpoints[1], weight)
+                        }),
+                    )
+                    .finish()
+            }
+        }
+        f.debug_struct("IndexGraph")
+            .field("directed", &self.directed)
+            .field("nodes", &self.node_weights)
+            .field(
+                "edges",
+                &EdgeList(&self.edges, self.edge_weights.as_slice()),
+            )
+            .finish()
+    }
+}
+
+/// Serialized as a `(directed, node_weights, [(from, to, edge_weight)])`
+/// tuple.
+#[cfg(feature = "serde")]
+impl<N, E, NW, EW> Serialize for IndexGraph<N, E, NW, EW>
+where
+    NW: Serialize,
+    EW: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct EdgeList<'a, EW>(&'a [EdgeLinks], &'a [EW]);
+        impl<EW: Serialize> Serialize for EdgeList<'_, EW> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(
+                    self.0
+                        .iter()
+                        .zip(self.1)
+                        .map(|(links, weight)| (links.endpoints[0], links.endpoints[1], weight)),
+                )
+            }
+        }
+        (
+            self.directed,
+            &self.node_weights,
+            EdgeList(&self.edges, self.edge_weights.as_slice()),
+        )
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N, E, NW, EW> Deserialize<'de> for IndexGraph<N, E, NW, EW>
+where
+    N: Idx,
+    E: Idx,
+    NW: Deserialize<'de>,
+    EW: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (directed, node_weights, edges) =
+            <(bool, Vec<NW>, Vec<(usize, usize, EW)>)>::deserialize(deserializer)?;
+        let mut res = Self::new_with_mode(directed);
+        res.reserve(node_weights.len(), edges.len());
+        for weight in node_weights {
+            let _ = res.add_node(weight);
+        }
+        for (from, to, weight) in edges {
+            if from.max(to) >= res.node_count() {
+                return Err(serde::de::Error::custom(format_args!(
+                    "edge endpoint {} out of bounds for {} nodes",
+                    from.max(to),
+                    res.node_count()
+                )));
+            }
+            let _ = res.add_edge(N::from_usize(from), N::from_usize(to), weight);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexGraph;
+
+    #[test]
+    fn swap_remove_reports_moved_ids() {
+        let mut g = IndexGraph::<usize, usize, char, u32>::new();
+        let [a, b, c] = ['a', 'b', 'c'].map(|w| g.add_node(w));
+        let ab = g.add_edge(a, b, 0);
+        let bc = g.add_edge(b, c, 1);
+        let ca = g.add_edge(c, a, 2);
+
+        assert_eq!(g.remove_edge(ab), (0, Some(ca)));
+        // `ca` now lives in the slot of `ab`
+        assert_eq!(g.edge_endpoints(ab), (c, a));
+        assert!(g.incoming(a).eq([(ab, c)]));
+        assert!(g.outgoing(b).eq([(bc, c)]));
+
+        assert_eq!(g.remove_node(a), ('a', Some(c)));
+        // `c` now lives in the slot of `a`, its edge to `a` is gone
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.edge_endpoints(0), (b, a));
+        assert!(g.incoming(a).eq([(0, b)]));
+        assert_eq!(g.node_weights().as_slice(), ['c', 'b']);
+    }
+
+    #[test]
+    fn undirected_adjacency() {
+        let mut g = IndexGraph::<u32, u32, (), ()>::new_undirected();
+        let [a, b] = [(); 2].map(|()| g.add_node(()));
+        let ab = g.add_edge(a, b, ());
+        let aa = g.add_edge(a, a, ());
+        assert!(g.outgoing(a).eq([(aa, a), (ab, b)]));
+        assert!(g.incoming(b).eq([(ab, a)]));
+        assert_eq!(g.find_edge(b, a), Some(ab));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let mut g = IndexGraph::<u32, u32, char, i32>::new();
+        let [a, b] = ['a', 'b'].map(|w| g.add_node(w));
+        let _ = g.add_edge(a, b, 7);
+        let json = serde_json::to_string(&g).unwrap();
+        assert_eq!(json, r#"[true,["a","b"],[[0,1,7]]]"#);
+        let g2 = serde_json::from_str::<IndexGraph<u32, u32, char, i32>>(&json).unwrap();
+        assert!(g2.outgoing(a).eq([(0, b)]));
+        assert!(
+            serde_json::from_str::<IndexGraph<u32, u32, char, i32>>(r#"[true,["a"],[[0,1,7]]]"#)
+                .is_err()
+        );
+    }
+}
@@ -433,12 +433,64 @@ impl<I, K, V, S> IndexHashMap<I, K, V, S> {
         self.data.get_index_mut(i.into_usize())
     }
 
+    pub fn insert_full(&mut self, key: K, value: V) -> (I, Option<V>)
+    where
+        I: Idx,
+        K: Hash + Eq,
+        S: BuildHasher,
+    {
+        let (i, prev) = self.data.insert_full(key, value);
+        (I::from_usize(i), prev)
+    }
+
     pub fn swap_remove<Q: ?Sized + Hash + Equivalent<K>>(&mut self, key: &Q) -> Option<V>
     where
         S: BuildHasher,
     {
         self.data.swap_remove(key)
     }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    pub fn shift_remove<Q: ?Sized + Hash + Equivalent<K>>(&mut self, key: &Q) -> Option<V>
+    where
+        S: BuildHasher,
+    {
+        self.data.shift_remove(key)
+    }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    /// Returns its key and its value.
+    pub fn shift_remove_entry<Q: ?Sized + Hash + Equivalent<K>>(
+        &mut self,
+        key: &Q,
+    ) -> Option<(K, V)>
+    where
+        S: BuildHasher,
+    {
+        self.data.shift_remove_entry(key)
+    }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    /// Returns the index the entry had, its key and its value.
+    pub fn shift_remove_full<Q: ?Sized + Hash + Equivalent<K>>(
+        &mut self,
+        key: &Q,
+    ) -> Option<(I, K, V)>
+    where
+        I: Idx,
+        S: BuildHasher,
+    {
+        let (i, k, v) = self.data.shift_remove_full(key)?;
+        Some((I::from_usize(i), k, v))
+    }
+
+    /// Removes the entry at `index`, shifting all following entries.
+    pub fn shift_remove_index(&mut self, index: I) -> Option<(K, V)>
+    where
+        I: Idx,
+    {
+        self.data.shift_remove_index(index.into_usize())
+    }
     pub fn as_index_map(&self) -> &IndexMap<K, V, S> {
         &self.data
     }
@@ -0,0 +1,450 @@
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use heapless::{
+    Deque,
+    deque::{Iter, IterMut},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{IdxCompat, IndexArray, index_enumerate::IndexEnumerate};
+
+use super::{idx::Idx, index_range::IndexRange, index_slice::IndexSlice};
+
+/// A fixed capacity ring buffer, the no-alloc counterpart to
+/// [`IndexVecDeque`](crate::IndexVecDeque).
+#[repr(transparent)]
+pub struct IndexHeaplessDeque<I, T, const CAP: usize> {
+    data: Deque<T, CAP>,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T, const CAP: usize> IndexHeaplessDeque<I, T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            data: Deque::new(),
+            _phantom: PhantomData,
+        }
+    }
+    pub fn get<X>(&self, index: X) -> Option<&T>
+    where
+        X: IdxCompat<I>,
+    {
+        self.data.get(index.into_usize())
+    }
+    pub fn get_mut<X>(&mut self, index: X) -> Option<&mut T>
+    where
+        X: IdxCompat<I>,
+    {
+        self.data.get_mut(index.into_usize())
+    }
+    pub fn swap(&mut self, i: I, j: I)
+    where
+        I: Idx,
+    {
+        self.data.swap(i.into_usize(), j.into_usize());
+    }
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.data.iter()
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, Iter<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &self.data)
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, IterMut<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &mut self.data)
+    }
+    pub fn as_index_slices(&self) -> (&IndexSlice<I, T>, &IndexSlice<I, T>) {
+        let (s1, s2) = self.data.as_slices();
+        (IndexSlice::from_slice(s1), IndexSlice::from_slice(s2))
+    }
+    pub fn as_mut_index_slices(&mut self) -> (&mut IndexSlice<I, T>, &mut IndexSlice<I, T>) {
+        let (s1, s2) = self.data.as_mut_slices();
+        (
+            IndexSlice::from_mut_slice(s1),
+            IndexSlice::from_mut_slice(s2),
+        )
+    }
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.data.as_slices()
+    }
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.data.as_mut_slices()
+    }
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.data.len())
+    }
+    pub fn last_idx(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.len().checked_sub(1).map(I::from_usize)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.data.iter().any(|v| v == x)
+    }
+    pub fn front(&self) -> Option<&T> {
+        self.data.front()
+    }
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.data.front_mut()
+    }
+    pub fn back(&self) -> Option<&T> {
+        self.data.back()
+    }
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.data.back_mut()
+    }
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.data.pop_front()
+    }
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.data.pop_back()
+    }
+    pub fn pop_front_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        self.data.pop_front_if(predicate)
+    }
+    pub fn pop_back_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        self.data.pop_back_if(predicate)
+    }
+    /// Returns the element back if the deque is full.
+    pub fn push_front(&mut self, v: T) -> Result<(), T> {
+        self.data.push_front(v)
+    }
+    /// Returns the element back if the deque is full.
+    pub fn push_back(&mut self, v: T) -> Result<(), T> {
+        self.data.push_back(v)
+    }
+    pub fn push_back_get_idx(&mut self, v: T) -> Result<I, T>
+    where
+        I: Idx,
+    {
+        let idx = self.len_idx();
+        self.data.push_back(v)?;
+        Ok(idx)
+    }
+    pub fn swap_remove_front(&mut self, idx: I) -> Option<T>
+    where
+        I: Idx,
+    {
+        self.data.swap_remove_front(idx.into_usize())
+    }
+    pub fn swap_remove_back(&mut self, idx: I) -> Option<T>
+    where
+        I: Idx,
+    {
+        self.data.swap_remove_back(idx.into_usize())
+    }
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.data.retain(f);
+    }
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.data.retain_mut(f);
+    }
+    pub fn make_contiguous(&mut self) -> &mut IndexSlice<I, T> {
+        IndexSlice::from_mut_slice(self.data.make_contiguous())
+    }
+    pub fn as_heapless_deque(&self) -> &Deque<T, CAP> {
+        &self.data
+    }
+    pub fn as_mut_heapless_deque(&mut self) -> &mut Deque<T, CAP> {
+        &mut self.data
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, heapless::deque::IntoIter<T, CAP>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data)
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+    pub fn from_index_array<const N: usize>(arr: IndexArray<I, T, N>) -> Self {
+        Self::from(arr.into_array())
+    }
+    pub const fn from_heapless_deque(v: Deque<T, CAP>) -> Self {
+        Self {
+            data: v,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn into_heapless_deque(self) -> Deque<T, CAP> {
+        self.data
+    }
+
+    #[cfg(feature = "serde")]
+    /// Use with [`serde(serialize_with = "path")`](https://serde.rs/field-attrs.html#serialize_with)
+    /// to serialize as a map instead of an array.
+    pub fn serialize_as_map<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        I: Idx + Serialize,
+        T: Serialize,
+    {
+        serializer.collect_map(self.iter_enumerated())
+    }
+}
+
+impl<I, T: Clone, const CAP: usize> Clone for IndexHeaplessDeque<I, T, CAP> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.clone())
+    }
+}
+
+impl<I, T: Debug, const CAP: usize> Debug for IndexHeaplessDeque<I, T, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I, T, const CAP: usize> Default for IndexHeaplessDeque<I, T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> Extend<T> for IndexHeaplessDeque<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I, T: 'a + Copy, const CAP: usize> Extend<&'a T> for IndexHeaplessDeque<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = &'a T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+/// # Panics
+/// Panics if `N` exceeds the capacity.
+impl<I, T, const N: usize, const CAP: usize> From<[T; N]> for IndexHeaplessDeque<I, T, CAP> {
+    fn from(value: [T; N]) -> Self {
+        let mut res = Self::new();
+        res.extend(value);
+        res
+    }
+}
+
+/// # Panics
+/// Panics if `N` exceeds the capacity.
+impl<I, T, const N: usize, const CAP: usize> From<IndexArray<I, T, N>>
+    for IndexHeaplessDeque<I, T, CAP>
+{
+    fn from(value: IndexArray<I, T, N>) -> Self {
+        Self::from_index_array(value)
+    }
+}
+
+impl<I, T, const CAP: usize> From<Deque<T, CAP>> for IndexHeaplessDeque<I, T, CAP> {
+    fn from(value: Deque<T, CAP>) -> Self {
+        Self::from_heapless_deque(value)
+    }
+}
+
+impl<I, T, const CAP: usize> From<IndexHeaplessDeque<I, T, CAP>> for Deque<T, CAP> {
+    fn from(value: IndexHeaplessDeque<I, T, CAP>) -> Self {
+        value.data
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> FromIterator<T> for IndexHeaplessDeque<I, T, CAP> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<I, T: Hash, const CAP: usize> Hash for IndexHeaplessDeque<I, T, CAP> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|v| v.hash(state));
+    }
+}
+
+impl<I, X, T, const CAP: usize> Index<X> for IndexHeaplessDeque<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    type Output = T;
+    #[inline]
+    fn index(&self, index: X) -> &Self::Output {
+        self.data
+            .get(index.into_usize())
+            .expect("IndexHeaplessDeque index out of bounds")
+    }
+}
+
+impl<I, X, T, const CAP: usize> IndexMut<X> for IndexHeaplessDeque<I, T, CAP>
+where
+    X: IdxCompat<I>,
+{
+    #[inline]
+    fn index_mut(&mut self, index: X) -> &mut Self::Output {
+        self.data
+            .get_mut(index.into_usize())
+            .expect("IndexHeaplessDeque index out of bounds")
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a IndexHeaplessDeque<I, T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a mut IndexHeaplessDeque<I, T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+impl<I, T, const CAP: usize> IntoIterator for IndexHeaplessDeque<I, T, CAP> {
+    type Item = T;
+    type IntoIter = heapless::deque::IntoIter<T, CAP>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<I, T, U, const CAP: usize, const CAP2: usize> PartialEq<IndexHeaplessDeque<I, U, CAP2>>
+    for IndexHeaplessDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &IndexHeaplessDeque<I, U, CAP2>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<I, T, U, const CAP: usize> PartialEq<[U]> for IndexHeaplessDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<I, T, U, const CAP: usize, const N: usize> PartialEq<[U; N]> for IndexHeaplessDeque<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == *other.as_slice()
+    }
+}
+
+impl<I, T: Eq, const CAP: usize> Eq for IndexHeaplessDeque<I, T, CAP> {}
+
+impl<I, T: PartialOrd, const CAP: usize> PartialOrd for IndexHeaplessDeque<I, T, CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<I, T: Ord, const CAP: usize> Ord for IndexHeaplessDeque<I, T, CAP> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I, T, const CAP: usize> Serialize for IndexHeaplessDeque<I, T, CAP>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T, const CAP: usize> Deserialize<'de> for IndexHeaplessDeque<I, T, CAP>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(Deque::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexHeaplessDeque;
+
+    #[test]
+    fn ring_buffer() {
+        let mut d = IndexHeaplessDeque::<u32, i32, 3>::from([1, 2]);
+        d.push_front(0).unwrap();
+        assert_eq!(d.push_back(3), Err(3));
+        assert_eq!(d.pop_front(), Some(0));
+        assert_eq!(d.push_back_get_idx(3), Ok(2));
+        assert_eq!(d[2], 3);
+        assert_eq!(d, [1, 2, 3]);
+        assert!(d.iter_enumerated().eq([(0, &1), (1, &2), (2, &3)]));
+    }
+}
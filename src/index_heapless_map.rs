@@ -0,0 +1,468 @@
+use core::{
+    borrow::Borrow,
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use heapless::index_map::{Entry, FnvIndexMap, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+
+use crate::{index_enumerate::IndexEnumerate, index_range::IndexRange};
+
+use super::idx::Idx;
+
+/// Create an [`IndexHeaplessMap`] containing the arguments.
+///
+/// The index type and capacity cannot be inferred from the macro so you
+/// might have to add type annotations.
+///
+/// # Panics
+/// Panics if the number of distinct keys exceeds the capacity.
+///
+/// # Example
+/// ```
+/// use indexland::{IndexHeaplessMap, index_heapless_map};
+/// let map: IndexHeaplessMap<u32, _, _, 4> = index_heapless_map! {
+///     "a" => 17,
+///     "b" => 42,
+/// };
+/// assert_eq!(map.get_index_of("b"), Some(1));
+/// ```
+#[macro_export]
+macro_rules! index_heapless_map {
+    () => {
+        $crate::IndexHeaplessMap::new()
+    };
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        let mut map = $crate::IndexHeaplessMap::new();
+        $(
+            let _ = map.insert($key, $value).ok().expect("IndexHeaplessMap capacity exceeded");
+        )*
+        map
+    }};
+}
+
+/// A fixed capacity insertion ordered hash map backed by
+/// [`FnvIndexMap`], the no-alloc counterpart to
+/// [`IndexHashMap`](crate::IndexHashMap).
+///
+/// `CAP` must be a power of two greater than one.
+#[repr(transparent)]
+pub struct IndexHeaplessMap<I, K, V, const CAP: usize> {
+    data: FnvIndexMap<K, V, CAP>,
+    _phantom: PhantomData<fn(I) -> (K, V)>,
+}
+
+impl<I, K, V, const CAP: usize> From<FnvIndexMap<K, V, CAP>> for IndexHeaplessMap<I, K, V, CAP> {
+    fn from(v: FnvIndexMap<K, V, CAP>) -> Self {
+        Self {
+            data: v,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, K, V, const CAP: usize> From<IndexHeaplessMap<I, K, V, CAP>> for FnvIndexMap<K, V, CAP> {
+    fn from(v: IndexHeaplessMap<I, K, V, CAP>) -> Self {
+        v.data
+    }
+}
+
+impl<I, K, V, const CAP: usize> Default for IndexHeaplessMap<I, K, V, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, K: Clone, V: Clone, const CAP: usize> Clone for IndexHeaplessMap<I, K, V, CAP> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.clone())
+    }
+}
+
+impl<I, K: Debug, V: Debug, const CAP: usize> Debug for IndexHeaplessMap<I, K, V, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I, K, V, const CAP: usize> IndexHeaplessMap<I, K, V, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            data: FnvIndexMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.data.len())
+    }
+    pub fn last_idx(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.len().checked_sub(1).map(I::from_usize)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.data.iter()
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.data.iter_mut()
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, Iter<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &self.data)
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, IterMut<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &mut self.data)
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, IntoIter<K, V, CAP>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data)
+    }
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.data.keys()
+    }
+    pub fn keys_enumerated(&self) -> IndexEnumerate<I, Keys<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data.keys())
+    }
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.data.values()
+    }
+    pub fn values_enumerated(&self) -> IndexEnumerate<I, Values<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data.values())
+    }
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.data.values_mut()
+    }
+    pub fn values_mut_enumerated(&mut self) -> IndexEnumerate<I, ValuesMut<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data.values_mut())
+    }
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.data.first()
+    }
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.data.last()
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    pub fn as_heapless_map(&self) -> &FnvIndexMap<K, V, CAP> {
+        &self.data
+    }
+    pub fn as_mut_heapless_map(&mut self) -> &mut FnvIndexMap<K, V, CAP> {
+        &mut self.data
+    }
+    pub fn into_heapless_map(self) -> FnvIndexMap<K, V, CAP> {
+        self.data
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+}
+
+impl<I, K, V, const CAP: usize> IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq,
+{
+    /// Returns the key-value pair back if the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        self.data.insert(key, value)
+    }
+
+    /// Like [`insert`](Self::insert), but also returns the index of the
+    /// entry.
+    pub fn insert_full(&mut self, key: K, value: V) -> Result<(I, Option<V>), (K, V)>
+    where
+        I: Idx,
+    {
+        if let Some(idx) = self.data.get_index_of(&key) {
+            let (_, slot) = self.data.get_index_mut(idx).unwrap();
+            return Ok((I::from_usize(idx), Some(core::mem::replace(slot, value))));
+        }
+        let idx = self.data.len();
+        let _ = self.data.insert(key, value)?;
+        Ok((I::from_usize(idx), None))
+    }
+
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn truncate_len(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.data.retain(f);
+    }
+    pub fn get_index(&self, i: I) -> Option<(&K, &V)>
+    where
+        I: Idx,
+    {
+        self.data.get_index(i.into_usize())
+    }
+    pub fn get_index_mut(&mut self, i: I) -> Option<(&K, &mut V)>
+    where
+        I: Idx,
+    {
+        self.data.get_index_mut(i.into_usize())
+    }
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, CAP> {
+        self.data.entry(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.data.contains_key(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.data.get(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.data.get_mut(key)
+    }
+
+    /// Return item index, if it exists in the map
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<I>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.data.get_index_of(key).map(I::from_usize)
+    }
+
+    /// Return item index, key and value
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(I, &K, &V)>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let idx = self.data.get_index_of(key)?;
+        let (key, value) = self.data.get_index(idx)?;
+        Some((I::from_usize(idx), key, value))
+    }
+
+    pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.data.swap_remove(key)
+    }
+}
+
+impl<I, K, V, const N: usize, const CAP: usize> TryFrom<[(K, V); N]>
+    for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq,
+{
+    type Error = (K, V);
+
+    fn try_from(value: [(K, V); N]) -> Result<Self, Self::Error> {
+        let mut res = Self::new();
+        for (k, v) in value {
+            let _ = res.insert(k, v)?;
+        }
+        Ok(res)
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, K: Hash + Eq, V, const CAP: usize> Extend<(K, V)> for IndexHeaplessMap<I, K, V, CAP> {
+    fn extend<It: IntoIterator<Item = (K, V)>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I, K, V, const CAP: usize> Extend<(&'a K, &'a V)> for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq + Copy,
+    V: Copy,
+{
+    fn extend<It: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, K: Hash + Eq, V, const CAP: usize> FromIterator<(K, V)> for IndexHeaplessMap<I, K, V, CAP> {
+    fn from_iter<It: IntoIterator<Item = (K, V)>>(iter: It) -> Self {
+        Self::from(FnvIndexMap::from_iter(iter))
+    }
+}
+
+impl<I, K, V, const CAP: usize> IntoIterator for IndexHeaplessMap<I, K, V, CAP> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, I, K, V, const CAP: usize> IntoIterator for &'a IndexHeaplessMap<I, K, V, CAP> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, I, K, V, const CAP: usize> IntoIterator for &'a mut IndexHeaplessMap<I, K, V, CAP> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+impl<I, K, V, Q, const CAP: usize> Index<&Q> for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+{
+    type Output = V;
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found")
+    }
+}
+
+impl<I, K, V, Q, const CAP: usize> IndexMut<&Q> for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+{
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("key not found")
+    }
+}
+
+impl<K, I1, V1, const CAP1: usize, I2, V2, const CAP2: usize>
+    PartialEq<IndexHeaplessMap<I2, K, V2, CAP2>> for IndexHeaplessMap<I1, K, V1, CAP1>
+where
+    K: Hash + Eq,
+    V1: PartialEq<V2>,
+{
+    fn eq(&self, other: &IndexHeaplessMap<I2, K, V2, CAP2>) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<I, K: Hash + Eq, V: Eq, const CAP: usize> Eq for IndexHeaplessMap<I, K, V, CAP> {}
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+impl<I, K, V, const CAP: usize> Serialize for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, K, V, const CAP: usize> Deserialize<'de> for IndexHeaplessMap<I, K, V, CAP>
+where
+    K: Hash + Eq + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(FnvIndexMap::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexHeaplessMap;
+
+    #[test]
+    fn typed_positions() {
+        let mut map: IndexHeaplessMap<u32, _, _, 4> = index_heapless_map! {
+            "a" => 1,
+            "b" => 2,
+        };
+        assert_eq!(map.insert_full("c", 3), Ok((2, None)));
+        assert_eq!(map.insert_full("a", 4), Ok((0, Some(1))));
+        assert_eq!(map.insert("d", 5), Ok(None));
+        assert_eq!(map.insert("e", 6), Err(("e", 6)));
+        assert_eq!(map.get_full("d"), Some((3, &"d", &5)));
+        assert_eq!(map.swap_remove("a"), Some(4));
+        assert_eq!(map.get_index_of("d"), Some(0));
+        assert!(map.keys_enumerated().eq([(0, &"d"), (1, &"b"), (2, &"c")]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let map: IndexHeaplessMap<u32, u32, i32, 2> = [(3, 1), (1, 2)].into_iter().collect();
+        let s = serde_json::to_string(&map).unwrap();
+        assert_eq!(s, r#"{"3":1,"1":2}"#);
+        let map2: IndexHeaplessMap<u32, u32, i32, 2> = serde_json::from_str(&s).unwrap();
+        assert_eq!(map, map2);
+    }
+}
@@ -0,0 +1,364 @@
+use core::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Deref,
+    str::Chars,
+};
+
+use heapless::{CapacityError, String, Vec};
+
+use crate::{IndexRange, IndexRangeBounds};
+
+use super::{idx::Idx, index_slice::IndexSlice};
+
+/// A fixed capacity UTF-8 string addressed by typed byte offsets.
+///
+/// The string derefs to [`str`], [`as_index_slice`](Self::as_index_slice)
+/// provides typed access to the underlying bytes.
+///
+/// # Example
+/// ```
+/// use indexland::{Idx, IndexHeaplessString};
+///
+/// #[derive(Idx)]
+/// struct BytePos(u16);
+///
+/// let mut s = IndexHeaplessString::<BytePos, 16>::try_from("hello").unwrap();
+/// let end = s.len_idx();
+/// s.push_str(" world").unwrap();
+/// assert_eq!(s.get(BytePos(0)..end), Some("hello"));
+/// assert_eq!(s.as_index_slice()[BytePos(6)], b'w');
+/// ```
+#[repr(transparent)]
+pub struct IndexHeaplessString<I, const CAP: usize> {
+    data: String<CAP>,
+    _phantom: PhantomData<fn(I) -> u8>,
+}
+
+impl<I, const CAP: usize> IndexHeaplessString<I, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            data: String::new(),
+            _phantom: PhantomData,
+        }
+    }
+    /// Fails if `bytes` is not valid UTF-8.
+    pub fn from_utf8(bytes: Vec<u8, CAP>) -> Result<Self, core::str::Utf8Error> {
+        Ok(Self::from(String::from_utf8(bytes)?))
+    }
+    pub fn into_bytes(self) -> Vec<u8, CAP> {
+        self.data.into_bytes()
+    }
+    pub fn as_str(&self) -> &str {
+        self.data.as_str()
+    }
+    pub fn as_mut_str(&mut self) -> &mut str {
+        self.data.as_mut_str()
+    }
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+    pub fn as_index_slice(&self) -> &IndexSlice<I, u8> {
+        IndexSlice::from_slice(self.data.as_bytes())
+    }
+    pub fn as_heapless_string(&self) -> &String<CAP> {
+        &self.data
+    }
+    pub fn as_mut_heapless_string(&mut self) -> &mut String<CAP> {
+        &mut self.data
+    }
+    pub fn into_heapless_string(self) -> String<CAP> {
+        self.data
+    }
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+    pub fn is_full(&self) -> bool {
+        self.data.len() == CAP
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.data.len())
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.data.push(c)
+    }
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.data.push_str(s)
+    }
+    /// Like [`push_str`](Self::push_str), but returns the byte offset the
+    /// string was inserted at.
+    pub fn push_str_get_idx(&mut self, s: &str) -> Result<I, CapacityError>
+    where
+        I: Idx,
+    {
+        let idx = self.len_idx();
+        self.data.push_str(s)?;
+        Ok(idx)
+    }
+    pub fn pop(&mut self) -> Option<char> {
+        self.data.pop()
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn insert(&mut self, idx: I, c: char) -> Result<(), CapacityError>
+    where
+        I: Idx,
+    {
+        self.data.insert(idx.into_usize(), c)
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn insert_str(&mut self, idx: I, s: &str) -> Result<(), CapacityError>
+    where
+        I: Idx,
+    {
+        self.data.insert_str(idx.into_usize(), s)
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn remove(&mut self, idx: I) -> char
+    where
+        I: Idx,
+    {
+        self.data.remove(idx.into_usize())
+    }
+    /// # Panics
+    /// Panics if `end` is not on a char boundary.
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    /// Returns the substring within `range`, or `None` if the range is out
+    /// of bounds or does not lie on char boundaries.
+    pub fn get<R: IndexRangeBounds<I>>(&self, range: R) -> Option<&str> {
+        self.data.get(range.canonicalize(self.data.len()))
+    }
+    pub fn is_char_boundary(&self, idx: I) -> bool
+    where
+        I: Idx,
+    {
+        self.data.is_char_boundary(idx.into_usize())
+    }
+    pub fn char_indices(&self) -> CharIndices<'_, I> {
+        CharIndices {
+            offset: 0,
+            chars: self.data.chars(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`IndexHeaplessString::char_indices`].
+pub struct CharIndices<'a, I> {
+    offset: usize,
+    chars: Chars<'a>,
+    _phantom: PhantomData<fn() -> I>,
+}
+
+impl<I: Idx> Iterator for CharIndices<'_, I> {
+    type Item = (I, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.chars.next()?;
+        let idx = I::from_usize(self.offset);
+        self.offset += c.len_utf8();
+        Some((idx, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+impl<I, const CAP: usize> Deref for IndexHeaplessString<I, CAP> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<I, const CAP: usize> Default for IndexHeaplessString<I, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, const CAP: usize> Clone for IndexHeaplessString<I, CAP> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.clone())
+    }
+}
+
+impl<I, const CAP: usize> Debug for IndexHeaplessString<I, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I, const CAP: usize> Display for IndexHeaplessString<I, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.data, f)
+    }
+}
+
+impl<I, const CAP: usize> core::fmt::Write for IndexHeaplessString<I, CAP> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.data.write_str(s)
+    }
+}
+
+impl<I, const CAP: usize> From<String<CAP>> for IndexHeaplessString<I, CAP> {
+    fn from(value: String<CAP>) -> Self {
+        Self {
+            data: value,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, const CAP: usize> From<IndexHeaplessString<I, CAP>> for String<CAP> {
+    fn from(value: IndexHeaplessString<I, CAP>) -> Self {
+        value.data
+    }
+}
+
+impl<'a, I, const CAP: usize> TryFrom<&'a str> for IndexHeaplessString<I, CAP> {
+    type Error = CapacityError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Ok(Self::from(String::try_from(value)?))
+    }
+}
+
+impl<I, const CAP: usize> AsRef<str> for IndexHeaplessString<I, CAP> {
+    fn as_ref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<I, const CAP: usize> AsRef<[u8]> for IndexHeaplessString<I, CAP> {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+impl<I, const CAP: usize> AsRef<IndexSlice<I, u8>> for IndexHeaplessString<I, CAP> {
+    fn as_ref(&self) -> &IndexSlice<I, u8> {
+        self.as_index_slice()
+    }
+}
+
+impl<I, const CAP: usize> Borrow<str> for IndexHeaplessString<I, CAP> {
+    fn borrow(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<I, const CAP: usize, const CAP2: usize> PartialEq<IndexHeaplessString<I, CAP2>>
+    for IndexHeaplessString<I, CAP>
+{
+    fn eq(&self, other: &IndexHeaplessString<I, CAP2>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<I, const CAP: usize> PartialEq<str> for IndexHeaplessString<I, CAP> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<I, const CAP: usize> PartialEq<&str> for IndexHeaplessString<I, CAP> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<I, const CAP: usize> PartialEq<IndexHeaplessString<I, CAP>> for str {
+    fn eq(&self, other: &IndexHeaplessString<I, CAP>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<I, const CAP: usize> PartialEq<IndexHeaplessString<I, CAP>> for &str {
+    fn eq(&self, other: &IndexHeaplessString<I, CAP>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<I, const CAP: usize> Eq for IndexHeaplessString<I, CAP> {}
+
+impl<I, const CAP: usize> PartialOrd for IndexHeaplessString<I, CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I, const CAP: usize> Ord for IndexHeaplessString<I, CAP> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<I, const CAP: usize> Hash for IndexHeaplessString<I, CAP> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+impl<I, const CAP: usize> Serialize for IndexHeaplessString<I, CAP> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, const CAP: usize> Deserialize<'de> for IndexHeaplessString<I, CAP> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexHeaplessString;
+
+    #[test]
+    fn typed_byte_offsets() {
+        let mut s = IndexHeaplessString::<u32, 8>::try_from("aé").unwrap();
+        assert_eq!(s.push_str_get_idx("bc").ok(), Some(3));
+        assert!(s.push_str("defg").is_err());
+        assert!(
+            s.char_indices()
+                .eq([(0, 'a'), (1, 'é'), (3, 'b'), (4, 'c')])
+        );
+        assert_eq!(s.get(1..2), None);
+        assert_eq!(s.get(1..3), Some("é"));
+        assert_eq!(s.remove(1), 'é');
+        assert_eq!(s, "abc");
+    }
+}
@@ -0,0 +1,517 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use heapless::{CapacityError, Vec};
+
+use crate::{IdxCompat, IndexArray, IndexRange, IndexRangeBounds, index_enumerate::IndexEnumerate};
+
+use super::{idx::Idx, index_slice::IndexSlice};
+
+/// Create an [`IndexHeaplessVec`] containing the arguments.
+///
+/// The syntax is identical to [`index_array!`](crate::index_array!).
+///
+/// # Example
+/// ```
+/// use indexland::{IndexHeaplessVec, index_heapless_vec};
+///
+/// let v: IndexHeaplessVec<u32, i32, 8> = index_heapless_vec![-1, 2, 3];
+/// assert_eq!(v.capacity(), 8);
+/// ```
+#[macro_export]
+macro_rules! index_heapless_vec {
+    () => {
+        $crate::IndexHeaplessVec::from_array([])
+    };
+    ($value:expr; $count: expr) => {
+        $crate::IndexHeaplessVec::from_array([ $value; $count])
+    };
+    ($($value:expr),+ $(,)?) => {
+        $crate::IndexHeaplessVec::from_array([$($value),*])
+    };
+    ($($index:expr => $value:expr),* $(,)?) => {{
+        let indices = [ $($index as usize),* ];
+        let mut values = [ $($value),* ];
+        let data = $crate::__private::array_from_values_and_distinct_indices(
+            indices,
+            ::core::mem::ManuallyDrop::new(values)
+        );
+        $crate::IndexHeaplessVec::from_array(data)
+    }};
+}
+
+#[repr(transparent)]
+pub struct IndexHeaplessVec<I, T, const CAP: usize> {
+    data: Vec<T, CAP>,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T, const CAP: usize> Deref for IndexHeaplessVec<I, T, CAP> {
+    type Target = IndexSlice<I, T>;
+
+    fn deref(&self) -> &Self::Target {
+        IndexSlice::from_slice(&self.data)
+    }
+}
+impl<I, T, const CAP: usize> DerefMut for IndexHeaplessVec<I, T, CAP> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        IndexSlice::from_mut_slice(&mut self.data)
+    }
+}
+
+impl<I, T, const CAP: usize> From<Vec<T, CAP>> for IndexHeaplessVec<I, T, CAP> {
+    fn from(v: Vec<T, CAP>) -> Self {
+        Self {
+            data: v,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, T, const CAP: usize> From<IndexHeaplessVec<I, T, CAP>> for Vec<T, CAP> {
+    fn from(value: IndexHeaplessVec<I, T, CAP>) -> Self {
+        value.data
+    }
+}
+
+impl<I, T, const CAP: usize> From<[T; CAP]> for IndexHeaplessVec<I, T, CAP> {
+    fn from(value: [T; CAP]) -> Self {
+        Self::from_array(value)
+    }
+}
+
+impl<I, T, const CAP: usize> From<IndexArray<I, T, CAP>> for IndexHeaplessVec<I, T, CAP> {
+    fn from(value: IndexArray<I, T, CAP>) -> Self {
+        Self::from_index_array(value)
+    }
+}
+
+impl<I, T, const CAP: usize> Default for IndexHeaplessVec<I, T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T: Debug, const CAP: usize> Debug for IndexHeaplessVec<I, T, CAP> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I, T: Clone, const CAP: usize> Clone for IndexHeaplessVec<I, T, CAP> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.clone())
+    }
+}
+
+impl<I, T, const CAP: usize> IndexHeaplessVec<I, T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// unlike `From<[T; N]>::from`, this is a `const fn`
+    pub const fn from_array<const N: usize>(arr: [T; N]) -> Self {
+        Self {
+            data: Vec::from_array(arr),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// unlike `From<IndexArray<I, T, N>::from`, this is a `const fn`
+    pub const fn from_index_array<const N: usize>(arr: IndexArray<I, T, N>) -> Self {
+        Self::from_array(arr.into_array())
+    }
+
+    pub fn from_heapless_vec(v: Vec<T, CAP>) -> Self {
+        Self::from(v)
+    }
+    pub fn as_heapless_vec(&self) -> &Vec<T, CAP> {
+        &self.data
+    }
+    pub fn as_mut_heapless_vec(&mut self) -> &mut Vec<T, CAP> {
+        &mut self.data
+    }
+    pub fn into_heapless_vec(self) -> Vec<T, CAP> {
+        self.data
+    }
+
+    pub fn try_push(&mut self, v: T) -> Result<(), T> {
+        self.data.push(v)
+    }
+    /// # Panics
+    /// Panics if the vector is already full.
+    pub fn push(&mut self, v: T) {
+        assert!(
+            self.data.push(v).is_ok(),
+            "IndexHeaplessVec capacity of {CAP} exceeded"
+        );
+    }
+    pub fn try_push_get_idx(&mut self, v: T) -> Result<I, T>
+    where
+        I: Idx,
+    {
+        let id = self.len_idx();
+        self.data.push(v)?;
+        Ok(id)
+    }
+    pub fn push_get_idx(&mut self, v: T) -> I
+    where
+        I: Idx,
+    {
+        let id = self.len_idx();
+        self.push(v);
+        id
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+    pub fn try_insert(&mut self, idx: I, v: T) -> Result<(), T>
+    where
+        I: Idx,
+    {
+        self.data.insert(idx.into_usize(), v)
+    }
+    pub fn remove(&mut self, idx: I) -> T
+    where
+        I: Idx,
+    {
+        self.data.remove(idx.into_usize())
+    }
+    pub fn swap_remove(&mut self, idx: I) -> T
+    where
+        I: Idx,
+    {
+        self.data.swap_remove(idx.into_usize())
+    }
+    pub fn try_extend_from_slice(&mut self, slice: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        self.data.extend_from_slice(slice)
+    }
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.data.retain(f);
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn truncate_len(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, core::slice::Iter<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &*self.data)
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, core::slice::IterMut<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, &mut *self.data)
+    }
+    pub fn iter_enumerated_range<X, R: IndexRangeBounds<X>>(
+        &self,
+        range: R,
+    ) -> IndexEnumerate<I, core::slice::Iter<'_, T>>
+    where
+        I: Idx,
+        X: IdxCompat<I>,
+    {
+        let range = range.canonicalize(self.len());
+        IndexEnumerate::new(I::from_usize(range.start), &self.data[range])
+    }
+    pub fn iter_enumerated_range_mut<X, R: IndexRangeBounds<X>>(
+        &mut self,
+        range: R,
+    ) -> IndexEnumerate<I, core::slice::IterMut<'_, T>>
+    where
+        I: Idx,
+        X: IdxCompat<I>,
+    {
+        let range = range.canonicalize(self.len());
+        IndexEnumerate::new(I::from_usize(range.start), &mut self.data[range])
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, heapless::vec::IntoIter<T, CAP, usize>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data)
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+    pub fn as_index_slice(&self) -> &IndexSlice<I, T> {
+        IndexSlice::from_slice(&self.data)
+    }
+    pub fn as_mut_index_slice(&mut self) -> &mut IndexSlice<I, T> {
+        IndexSlice::from_mut_slice(&mut self.data)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Use with [`serde(serialize_with = "path")`](https://serde.rs/field-attrs.html#serialize_with)
+    /// to serialize as a map instead of an array.
+    pub fn serialize_as_map<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        I: Idx + Serialize,
+        T: Serialize,
+    {
+        serializer.collect_map(self.iter_enumerated())
+    }
+}
+
+impl<I, T, const CAP: usize> AsRef<[T]> for IndexHeaplessVec<I, T, CAP> {
+    fn as_ref(&self) -> &[T] {
+        &self.data
+    }
+}
+impl<I, T, const CAP: usize> AsRef<IndexSlice<I, T>> for IndexHeaplessVec<I, T, CAP> {
+    fn as_ref(&self) -> &IndexSlice<I, T> {
+        self.as_index_slice()
+    }
+}
+
+impl<I, T, const CAP: usize> AsMut<[T]> for IndexHeaplessVec<I, T, CAP> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+impl<I, T, const CAP: usize> AsMut<IndexSlice<I, T>> for IndexHeaplessVec<I, T, CAP> {
+    fn as_mut(&mut self) -> &mut IndexSlice<I, T> {
+        self.as_mut_index_slice()
+    }
+}
+
+impl<I, T, const CAP: usize> Borrow<[T]> for IndexHeaplessVec<I, T, CAP> {
+    fn borrow(&self) -> &[T] {
+        &self.data
+    }
+}
+impl<I, T, const CAP: usize> Borrow<IndexSlice<I, T>> for IndexHeaplessVec<I, T, CAP> {
+    fn borrow(&self) -> &IndexSlice<I, T> {
+        self.as_index_slice()
+    }
+}
+
+impl<I, T, const CAP: usize> BorrowMut<[T]> for IndexHeaplessVec<I, T, CAP> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+impl<I, T, const CAP: usize> BorrowMut<IndexSlice<I, T>> for IndexHeaplessVec<I, T, CAP> {
+    fn borrow_mut(&mut self) -> &mut IndexSlice<I, T> {
+        self.as_mut_index_slice()
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> Extend<T> for IndexHeaplessVec<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I, T: 'a + Copy, const CAP: usize> Extend<&'a T> for IndexHeaplessVec<I, T, CAP> {
+    fn extend<It: IntoIterator<Item = &'a T>>(&mut self, iter: It) {
+        self.data.extend(iter.into_iter().copied());
+    }
+}
+
+/// # Panics
+/// Panics if the capacity is exceeded.
+impl<I, T, const CAP: usize> FromIterator<T> for IndexHeaplessVec<I, T, CAP> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl<I, T, const CAP: usize> IntoIterator for IndexHeaplessVec<I, T, CAP> {
+    type Item = T;
+    type IntoIter = heapless::vec::IntoIter<T, CAP, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a IndexHeaplessVec<I, T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, I, T, const CAP: usize> IntoIterator for &'a mut IndexHeaplessVec<I, T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize, const N: usize> PartialEq<[T; N]>
+    for IndexHeaplessVec<I, T, CAP>
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize, const N: usize> PartialEq<IndexHeaplessVec<I, T, CAP>>
+    for [T; N]
+{
+    fn eq(&self, other: &IndexHeaplessVec<I, T, CAP>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize> PartialEq<[T]> for IndexHeaplessVec<I, T, CAP> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize> PartialEq<IndexHeaplessVec<I, T, CAP>> for [T] {
+    fn eq(&self, other: &IndexHeaplessVec<I, T, CAP>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize> PartialEq<IndexSlice<I, T>>
+    for IndexHeaplessVec<I, T, CAP>
+{
+    fn eq(&self, other: &IndexSlice<I, T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<I, T: PartialEq, const CAP: usize> PartialEq<IndexHeaplessVec<I, T, CAP>>
+    for IndexSlice<I, T>
+{
+    fn eq(&self, other: &IndexHeaplessVec<I, T, CAP>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<I, T, U, const CAP: usize, const CAP2: usize> PartialEq<IndexHeaplessVec<I, U, CAP2>>
+    for IndexHeaplessVec<I, T, CAP>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &IndexHeaplessVec<I, U, CAP2>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<I, T: Eq, const CAP: usize> Eq for IndexHeaplessVec<I, T, CAP> {}
+
+impl<I, T, U, const CAP: usize, const CAP2: usize> PartialOrd<IndexHeaplessVec<I, U, CAP2>>
+    for IndexHeaplessVec<I, T, CAP>
+where
+    T: PartialOrd<U>,
+{
+    fn partial_cmp(&self, other: &IndexHeaplessVec<I, U, CAP2>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<I, T: Ord, const CAP: usize> Ord for IndexHeaplessVec<I, T, CAP> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl<I, T: Hash, const CAP: usize> Hash for IndexHeaplessVec<I, T, CAP> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+impl<I, T, const CAP: usize> Serialize for IndexHeaplessVec<I, T, CAP>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T, const CAP: usize> Deserialize<'de> for IndexHeaplessVec<I, T, CAP>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(Vec::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexHeaplessVec;
+
+    #[test]
+    fn push_until_full() {
+        let mut v: IndexHeaplessVec<u32, i32, 3> = index_heapless_vec![1, 2];
+        assert_eq!(v.push_get_idx(3), 2);
+        assert_eq!(v.try_push(4), Err(4));
+        assert!(v.is_full());
+        assert_eq!(v.swap_remove(0), 1);
+        assert_eq!(v, [3, 2]);
+        assert!(v.iter_enumerated().eq([(0, &3), (1, &2)]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let v: IndexHeaplessVec<u32, i32, 4> = index_heapless_vec![1, 2, 3];
+        let s = serde_json::to_string(&v).unwrap();
+        assert_eq!(s, "[1,2,3]");
+        let v2: IndexHeaplessVec<u32, i32, 4> = serde_json::from_str(&s).unwrap();
+        assert_eq!(v, v2);
+        assert!(serde_json::from_str::<IndexHeaplessVec<u32, i32, 2>>(&s).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+//! Lazily initialized, append-only tables for memoization.
+//!
+//! [`IndexOnceVec`] behaves like an `IndexVec<I, OnceCell<T>>` of unbounded
+//! length. Slots are initialized individually through a shared reference using
+//! [`get_or_init`](IndexOnceVec::get_or_init), and the table grows on demand.
+//!
+//! Storage is split into buckets of doubling size that are never moved or
+//! freed while the table is shared, so references returned by earlier calls
+//! stay valid while the table grows.
+//!
+//! [`IndexOnceLockVec`] is the thread safe equivalent based on
+//! [`OnceLock`](std::sync::OnceLock) and requires the `std` feature.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexOnceVec};
+//!
+//! #[derive(Idx)]
+//! struct QueryId(u32);
+//!
+//! let memo = IndexOnceVec::<QueryId, String>::new();
+//!
+//! let a = memo.get_or_init(QueryId(3), || "expensive".to_string());
+//! // grows the table, but `a` stays valid
+//! let b = memo.get_or_init(QueryId(1000), || "result".to_string());
+//!
+//! assert_eq!(a, "expensive");
+//! assert_eq!(b, "result");
+//! assert_eq!(memo.get(QueryId(4)), None);
+//! ```
+
+use core::{cell::OnceCell, fmt::Debug, marker::PhantomData};
+
+use alloc::boxed::Box;
+
+use crate::Idx;
+
+/// log2 of the size of the first bucket.
+const FIRST_BUCKET_BITS: u32 = 5;
+
+/// Enough buckets to address every index up to `usize::MAX - 2^FIRST_BUCKET_BITS`.
+const BUCKET_COUNT: usize = (usize::BITS - FIRST_BUCKET_BITS) as usize;
+
+/// Maps a flat index to the bucket containing it and the offset within that
+/// bucket. Bucket `b` holds `2^(FIRST_BUCKET_BITS + b)` slots.
+/// Returns `None` for the topmost indices that no bucket can address.
+#[inline]
+fn bucket_location(index: usize) -> Option<(usize, usize)> {
+    let shifted = index.checked_add(1 << FIRST_BUCKET_BITS)?;
+    let bits = usize::BITS - 1 - shifted.leading_zeros();
+    let bucket = (bits - FIRST_BUCKET_BITS) as usize;
+    Some((bucket, shifted - (1 << bits)))
+}
+
+#[inline]
+fn bucket_start(bucket: usize) -> usize {
+    (1 << (FIRST_BUCKET_BITS as usize + bucket)) - (1 << FIRST_BUCKET_BITS)
+}
+
+macro_rules! impl_index_once_vec {
+    ($(#[$attrs: meta])* $name: ident, $cell: ident) => {
+        $(#[$attrs])*
+        pub struct $name<I, T> {
+            buckets: [$cell<Box<[$cell<T>]>>; BUCKET_COUNT],
+            _phantom: PhantomData<fn(I) -> T>,
+        }
+
+        impl<I, T> $name<I, T> {
+            pub const fn new() -> Self {
+                Self {
+                    buckets: [const { $cell::new() }; BUCKET_COUNT],
+                    _phantom: PhantomData,
+                }
+            }
+
+            fn slot(&self, index: usize) -> Option<&$cell<T>> {
+                let (bucket, offset) = bucket_location(index)?;
+                Some(&self.buckets[bucket].get()?[offset])
+            }
+
+            fn slot_or_alloc(&self, index: usize) -> &$cell<T> {
+                let Some((bucket, offset)) = bucket_location(index) else {
+                    panic!("index `{index}` is out of range for a once vec");
+                };
+                let bucket = self.buckets[bucket].get_or_init(|| {
+                    (0..1usize << (FIRST_BUCKET_BITS as usize + bucket))
+                        .map(|_| $cell::new())
+                        .collect()
+                });
+                &bucket[offset]
+            }
+
+            pub fn get(&self, idx: I) -> Option<&T>
+            where
+                I: Idx,
+            {
+                self.slot(idx.into_usize())?.get()
+            }
+
+            pub fn get_mut(&mut self, idx: I) -> Option<&mut T>
+            where
+                I: Idx,
+            {
+                let (bucket, offset) = bucket_location(idx.into_usize())?;
+                self.buckets[bucket].get_mut()?[offset].get_mut()
+            }
+
+            /// Returns the value at `idx`, initializing it with `f` if it was
+            /// empty. Allocates the slot's bucket if necessary.
+            pub fn get_or_init<F>(&self, idx: I, f: F) -> &T
+            where
+                I: Idx,
+                F: FnOnce() -> T,
+            {
+                self.slot_or_alloc(idx.into_usize()).get_or_init(f)
+            }
+
+            /// Initializes the slot at `idx` with `value`.
+            /// Returns `value` back if the slot was already initialized.
+            pub fn set(&self, idx: I, value: T) -> Result<(), T>
+            where
+                I: Idx,
+            {
+                self.slot_or_alloc(idx.into_usize()).set(value)
+            }
+
+            pub fn contains(&self, idx: I) -> bool
+            where
+                I: Idx,
+            {
+                self.get(idx).is_some()
+            }
+
+            /// Removes the value at `idx`, making the slot initializable again.
+            pub fn take(&mut self, idx: I) -> Option<T>
+            where
+                I: Idx,
+            {
+                let (bucket, offset) = bucket_location(idx.into_usize())?;
+                self.buckets[bucket].get_mut()?[offset].take()
+            }
+
+            /// Drops all values and frees all buckets.
+            pub fn clear(&mut self) {
+                for bucket in &mut self.buckets {
+                    let _ = bucket.take();
+                }
+            }
+
+            /// The number of slots that are currently allocated.
+            pub fn capacity(&self) -> usize {
+                self.buckets
+                    .iter()
+                    .filter_map(|bucket| bucket.get())
+                    .map(|bucket| bucket.len())
+                    .sum()
+            }
+
+            /// Iterates over all initialized slots in ascending index order.
+            pub fn iter_initialized_enumerated(&self) -> impl Iterator<Item = (I, &T)>
+            where
+                I: Idx,
+            {
+                self.buckets
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(b, bucket)| Some((bucket_start(b), bucket.get()?)))
+                    .flat_map(|(start, bucket)| {
+                        bucket.iter().enumerate().filter_map(move |(offset, slot)| {
+                            Some((I::from_usize(start + offset), slot.get()?))
+                        })
+                    })
+            }
+
+            /// Iterates over all initialized slots in ascending index order.
+            pub fn iter_initialized_enumerated_mut(
+                &mut self,
+            ) -> impl Iterator<Item = (I, &mut T)>
+            where
+                I: Idx,
+            {
+                self.buckets
+                    .iter_mut()
+                    .enumerate()
+                    .filter_map(|(b, bucket)| Some((bucket_start(b), bucket.get_mut()?)))
+                    .flat_map(|(start, bucket)| {
+                        bucket.iter_mut().enumerate().filter_map(move |(offset, slot)| {
+                            Some((I::from_usize(start + offset), slot.get_mut()?))
+                        })
+                    })
+            }
+        }
+
+        impl<I, T> Default for $name<I, T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<I, T> Debug for $name<I, T>
+        where
+            I: Idx + Debug,
+            T: Debug,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_map()
+                    .entries(self.iter_initialized_enumerated())
+                    .finish()
+            }
+        }
+
+        impl<I, T> FromIterator<(I, T)> for $name<I, T>
+        where
+            I: Idx,
+        {
+            fn from_iter<It: IntoIterator<Item = (I, T)>>(iter: It) -> Self {
+                let res = Self::new();
+                for (idx, value) in iter {
+                    // later values for the same index are dropped,
+                    // in line with `get_or_init` semantics
+                    let _ = res.set(idx, value);
+                }
+                res
+            }
+        }
+    };
+}
+
+impl_index_once_vec! {
+    /// Growable table of lazily initialized [`OnceCell`]s, see the
+    /// [module documentation](self).
+    IndexOnceVec, OnceCell
+}
+
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "std")]
+impl_index_once_vec! {
+    /// Thread safe version of [`IndexOnceVec`] based on [`OnceLock`].
+    ///
+    /// Concurrent calls to [`get_or_init`](Self::get_or_init) for the same
+    /// index block until the first initializer has finished.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    IndexOnceLockVec, OnceLock
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BUCKET_COUNT, IndexOnceVec, bucket_location, bucket_start};
+
+    #[test]
+    fn bucket_layout() {
+        assert_eq!(bucket_location(0), Some((0, 0)));
+        assert_eq!(bucket_location(31), Some((0, 31)));
+        assert_eq!(bucket_location(32), Some((1, 0)));
+        assert_eq!(bucket_location(95), Some((1, 63)));
+        assert_eq!(bucket_location(96), Some((2, 0)));
+        assert_eq!(bucket_start(2), 96);
+        assert_eq!(
+            bucket_location(usize::MAX - 32),
+            Some((BUCKET_COUNT - 1, (1 << (usize::BITS - 1)) - 1))
+        );
+        assert_eq!(bucket_location(usize::MAX - 31), None);
+    }
+
+    #[test]
+    fn unaddressable_index_is_absent() {
+        let mut v = IndexOnceVec::<usize, u8>::new();
+        assert_eq!(v.get(usize::MAX), None);
+        assert_eq!(v.get_mut(usize::MAX), None);
+        assert_eq!(v.take(usize::MAX), None);
+        assert!(!v.contains(usize::MAX));
+    }
+
+    #[test]
+    fn references_survive_growth() {
+        let v = IndexOnceVec::<usize, usize>::new();
+        let first = v.get_or_init(0, || 0);
+        for i in 1..1000 {
+            assert_eq!(*v.get_or_init(i * 7, || i), i);
+        }
+        assert_eq!(*first, 0);
+        assert_eq!(v.iter_initialized_enumerated().count(), 1000);
+        assert!(
+            v.iter_initialized_enumerated()
+                .take(3)
+                .eq([(0, &0), (7, &1), (14, &2)])
+        );
+    }
+}
@@ -57,8 +57,7 @@
 //! ```
 use crate::Idx;
 use core::ops::{
-    Add, Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
-    RangeToInclusive, Sub,
+    Add, Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
 };
 
 pub trait IndexRangeBounds<I>: RangeBounds<I> {
@@ -428,12 +427,12 @@ impl<I: Idx + Ord + Add<Output = I>> Iterator for IndexRange<I> {
         Some(curr)
     }
 }
-impl<I: Idx + Ord + Add<Output = I> + Sub<Output = I>> DoubleEndedIterator for IndexRange<I> {
+impl<I: Idx + Ord + Add<Output = I>> DoubleEndedIterator for IndexRange<I> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             return None;
         }
-        self.end = self.end - I::ONE;
+        self.end = I::from_usize(self.end.into_usize() - 1);
         Some(self.end)
     }
 }
@@ -453,9 +452,7 @@ impl<I: Idx + Ord + Add<Output = I>> Iterator for IndexRangeInclusive<I> {
         Some(curr)
     }
 }
-impl<I: Idx + Ord + Add<Output = I> + Sub<Output = I>> DoubleEndedIterator
-    for IndexRangeInclusive<I>
-{
+impl<I: Idx + Ord + Add<Output = I>> DoubleEndedIterator for IndexRangeInclusive<I> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let curr = self.end;
         if self.start == curr {
@@ -464,7 +461,7 @@ impl<I: Idx + Ord + Add<Output = I> + Sub<Output = I>> DoubleEndedIterator
             }
             self.exclusive = true;
         } else {
-            self.end = self.end - I::ONE;
+            self.end = I::from_usize(self.end.into_usize() - 1);
         }
         Some(curr)
     }
@@ -0,0 +1,433 @@
+//! A set of indices stored as disjoint ranges.
+//!
+//! [`IndexRangeSet`] keeps its ranges sorted, non-overlapping and
+//! non-adjacent, so inserting `0..3` and `3..5` results in the single range
+//! `0..5`. This makes it a good fit for liveness intervals, dirty regions and
+//! allocation maps.
+//!
+//! Ranges are stored as exclusive `usize` ranges and are clamped to
+//! `I::MAX` (inclusive). [`iter`](IndexRangeSet::iter) therefore yields
+//! [`RangeInclusive<I>`], which can represent ranges ending at `I::MAX`.
+//! The only exception is `usize::MAX` for `IndexRangeSet<usize>`, which
+//! can't be stored.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexRangeSet};
+//!
+//! #[derive(Idx)]
+//! struct InstId(u32);
+//!
+//! let mut live = IndexRangeSet::<InstId>::new();
+//! live.insert_range(InstId(0)..InstId(3));
+//! live.insert_range(InstId(3)..InstId(5));
+//! live.insert_range(InstId(8)..=InstId(9));
+//! live.remove_range(InstId(1)..InstId(2));
+//!
+//! assert_eq!(live.len(), 6);
+//! assert!(!live.contains(InstId(1)));
+//! assert_eq!(live.range_count(), 3);
+//!
+//! let dead = live.complement(InstId(0)..InstId(10));
+//! assert!(dead.iter().eq([InstId(1)..=InstId(1), InstId(5)..=InstId(7)]));
+//! ```
+
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Bound, Range, RangeBounds, RangeInclusive},
+};
+
+use alloc::vec::Vec;
+
+use crate::{Idx, IndexRangeBounds};
+
+pub struct IndexRangeSet<I> {
+    // sorted, non-empty, and separated by at least one index
+    ranges: Vec<Range<usize>>,
+    _phantom: PhantomData<fn(I) -> I>,
+}
+
+impl<I> IndexRangeSet<I> {
+    pub const fn new() -> Self {
+        Self {
+            ranges: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Builds a set from ranges that are sorted by their start,
+    /// merging overlapping and adjacent ones.
+    fn from_sorted_ranges(ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        let mut res = Self::new();
+        for r in ranges {
+            if r.is_empty() {
+                continue;
+            }
+            match res.ranges.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => res.ranges.push(r),
+            }
+        }
+        res
+    }
+
+    fn canonicalize<R: RangeBounds<I>>(range: &R) -> Range<usize>
+    where
+        I: Idx,
+    {
+        // exclusive bound that still allows `I::MAX` to be contained
+        let limit = I::MAX_USIZE.saturating_add(1);
+        let start = match range.start_bound() {
+            Bound::Included(s) => s.into_usize(),
+            Bound::Excluded(s) => s.into_usize().saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e.into_usize().saturating_add(1),
+            Bound::Excluded(e) => e.into_usize(),
+            Bound::Unbounded => limit,
+        };
+        start.min(limit)..end.min(limit)
+    }
+
+    /// The total number of indices contained in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(ExactSizeIterator::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of disjoint ranges the set consists of.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn contains(&self, idx: I) -> bool
+    where
+        I: Idx,
+    {
+        let idx = idx.into_usize();
+        let i = self.ranges.partition_point(|r| r.end <= idx);
+        self.ranges.get(i).is_some_and(|r| r.start <= idx)
+    }
+
+    /// Returns `true` if every index in `range` is contained in the set.
+    pub fn contains_range<R: IndexRangeBounds<I>>(&self, range: R) -> bool
+    where
+        I: Idx,
+    {
+        let range = Self::canonicalize(&range);
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn insert(&mut self, idx: I)
+    where
+        I: Idx,
+    {
+        self.insert_usize_range(Self::canonicalize(&(idx..=idx)));
+    }
+
+    pub fn remove(&mut self, idx: I)
+    where
+        I: Idx,
+    {
+        self.remove_usize_range(Self::canonicalize(&(idx..=idx)));
+    }
+
+    pub fn insert_range<R: IndexRangeBounds<I>>(&mut self, range: R)
+    where
+        I: Idx,
+    {
+        self.insert_usize_range(Self::canonicalize(&range));
+    }
+
+    pub fn remove_range<R: IndexRangeBounds<I>>(&mut self, range: R)
+    where
+        I: Idx,
+    {
+        self.remove_usize_range(Self::canonicalize(&range));
+    }
+
+    fn insert_usize_range(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        // all ranges that overlap or touch `range`
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        if first == last {
+            self.ranges.insert(first, range);
+            return;
+        }
+        let start = range.start.min(self.ranges[first].start);
+        let end = range.end.max(self.ranges[last - 1].end);
+        self.ranges[first] = start..end;
+        let _ = self.ranges.drain(first + 1..last);
+    }
+
+    fn remove_usize_range(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        // all ranges that overlap `range`
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+        let head = self.ranges[first].start..range.start;
+        let tail = range.end..self.ranges[last - 1].end;
+        let _ = self.ranges.splice(
+            first..last,
+            [head, tail].into_iter().filter(|r| !r.is_empty()),
+        );
+    }
+
+    /// Returns a set containing all indices that are in `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut a = self.ranges.iter().peekable();
+        let mut b = other.ranges.iter().peekable();
+        let merged = core::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.start < x.start => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        });
+        Self::from_sorted_ranges(merged.cloned())
+    }
+
+    /// Returns a set containing all indices that are in both `self` and
+    /// `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut res = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                res.ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        res
+    }
+
+    /// Returns a set containing all indices that are in `self` but not in
+    /// `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement_usize(0..usize::MAX))
+    }
+
+    /// Returns a set containing all indices within `bound` that are not in
+    /// `self`.
+    pub fn complement<R: IndexRangeBounds<I>>(&self, bound: R) -> Self
+    where
+        I: Idx,
+    {
+        self.complement_usize(Self::canonicalize(&bound))
+    }
+
+    fn complement_usize(&self, bound: Range<usize>) -> Self {
+        let mut res = Self::new();
+        let mut start = bound.start;
+        for r in &self.ranges {
+            if r.start >= bound.end {
+                break;
+            }
+            if start < r.start {
+                res.ranges.push(start..r.start);
+            }
+            start = start.max(r.end);
+        }
+        if start < bound.end {
+            res.ranges.push(start..bound.end);
+        }
+        res
+    }
+
+    /// Iterates over the disjoint ranges in ascending order.
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = RangeInclusive<I>> + ExactSizeIterator + '_
+    where
+        I: Idx,
+    {
+        self.ranges
+            .iter()
+            .map(|r| I::from_usize(r.start)..=I::from_usize(r.end - 1))
+    }
+
+    /// Iterates over all contained indices in ascending order.
+    pub fn iter_indices(&self) -> impl DoubleEndedIterator<Item = I> + '_
+    where
+        I: Idx,
+    {
+        self.ranges
+            .iter()
+            .flat_map(|r| r.clone().map(I::from_usize))
+    }
+}
+
+impl<I> Default for IndexRangeSet<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Clone for IndexRangeSet<I> {
+    fn clone(&self) -> Self {
+        Self {
+            ranges: self.ranges.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I: Idx + Debug> Debug for IndexRangeSet<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<I> PartialEq for IndexRangeSet<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges == other.ranges
+    }
+}
+
+impl<I> Eq for IndexRangeSet<I> {}
+
+impl<I> core::hash::Hash for IndexRangeSet<I> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.ranges.hash(state);
+    }
+}
+
+impl<I: Idx> Extend<I> for IndexRangeSet<I> {
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        for idx in iter {
+            self.insert(idx);
+        }
+    }
+}
+
+impl<I: Idx> Extend<RangeInclusive<I>> for IndexRangeSet<I> {
+    fn extend<It: IntoIterator<Item = RangeInclusive<I>>>(&mut self, iter: It) {
+        for range in iter {
+            self.insert_range(range);
+        }
+    }
+}
+
+impl<I: Idx> FromIterator<I> for IndexRangeSet<I> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<I: Idx> FromIterator<RangeInclusive<I>> for IndexRangeSet<I> {
+    fn from_iter<It: IntoIterator<Item = RangeInclusive<I>>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexRangeSet;
+
+    fn set(ranges: &[(usize, usize)]) -> IndexRangeSet<usize> {
+        let mut res = IndexRangeSet::new();
+        for &(start, end) in ranges {
+            res.insert_range(start..end);
+        }
+        res
+    }
+
+    fn ranges(set: &IndexRangeSet<usize>) -> alloc::vec::Vec<(usize, usize)> {
+        set.iter().map(|r| (*r.start(), *r.end() + 1)).collect()
+    }
+
+    #[test]
+    fn insert_and_remove_normalize() {
+        let mut s = set(&[(5, 7), (0, 2), (2, 3), (10, 12), (6, 11)]);
+        assert_eq!(ranges(&s), [(0, 3), (5, 12)]);
+        assert_eq!(s.len(), 10);
+        s.remove_range(1..=5);
+        assert_eq!(ranges(&s), [(0, 1), (6, 12)]);
+        s.remove_range(..);
+        assert!(s.is_empty());
+        s.insert(usize::MAX - 1);
+        assert!(s.contains(usize::MAX - 1));
+        assert!(s.iter_indices().eq([usize::MAX - 1]));
+    }
+
+    #[test]
+    fn contains_max() {
+        let mut s = IndexRangeSet::<u32>::new();
+        s.insert(u32::MAX);
+        assert!(s.contains(u32::MAX));
+        s.insert_range(u32::MAX - 1..);
+        assert!(s.iter_indices().eq([u32::MAX - 1, u32::MAX]));
+        assert_eq!(
+            alloc::format!("{s:?}"),
+            alloc::format!("{{{}..={}}}", u32::MAX - 1, u32::MAX)
+        );
+        assert_eq!(s.complement(..).len(), u32::MAX as usize - 1);
+        s.remove(u32::MAX);
+        assert_eq!(s.len(), 1);
+
+        let mut s = IndexRangeSet::<usize>::new();
+        s.insert(usize::MAX);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn contains_last_enum_variant() {
+        crate::idx_enum! {
+            enum Color { Red, Green, Blue }
+        }
+        let mut s = IndexRangeSet::<Color>::new();
+        s.insert(Color::Blue);
+        assert!(s.contains(Color::Blue));
+        assert!(!s.contains(Color::Green));
+        assert!(s.iter().eq([Color::Blue..=Color::Blue]));
+        assert!(
+            s.complement(..)
+                .iter_indices()
+                .eq([Color::Red, Color::Green])
+        );
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a = set(&[(0, 4), (6, 10)]);
+        let b = set(&[(2, 7), (9, 12)]);
+        assert_eq!(ranges(&a.union(&b)), [(0, 12)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(2, 4), (6, 7), (9, 10)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 2), (7, 9)]);
+        assert_eq!(ranges(&b.difference(&a)), [(4, 6), (10, 12)]);
+        assert_eq!(ranges(&a.complement(1..8)), [(4, 6)]);
+        assert!(a.contains_range(6..10));
+        assert!(!a.contains_range(3..7));
+    }
+}
@@ -0,0 +1,437 @@
+//! A queue whose indices stay valid across [`pop_front`](IndexSeqQueue::pop_front).
+//!
+//! Unlike [`IndexVecDeque`](crate::IndexVecDeque), where index zero is always
+//! the current front, every element of an [`IndexSeqQueue`] keeps the
+//! sequence number it was pushed with until it is removed.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexSeqQueue};
+//!
+//! #[derive(Idx)]
+//! struct MsgId(u32);
+//!
+//! let mut log = IndexSeqQueue::<MsgId, &str>::new();
+//! let hello = log.push_back("hello");
+//! let world = log.push_back("world");
+//!
+//! assert_eq!(log.pop_front(), Some("hello"));
+//! assert_eq!(log.get(hello), None);
+//! assert_eq!(log[world], "world");
+//! assert_eq!(log.front_idx(), MsgId(1));
+//! ```
+
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use alloc::collections::{VecDeque, vec_deque};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{IdxCompat, index_enumerate::IndexEnumerate};
+
+use super::{idx::Idx, index_range::IndexRange};
+
+pub struct IndexSeqQueue<I, T> {
+    data: VecDeque<T>,
+    // sequence number of the front element
+    base: usize,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T> IndexSeqQueue<I, T> {
+    pub const fn new() -> Self {
+        Self {
+            data: VecDeque::new(),
+            base: 0,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            data: VecDeque::with_capacity(cap),
+            base: 0,
+            _phantom: PhantomData,
+        }
+    }
+    /// Creates an empty queue whose first element will receive `front`.
+    pub fn starting_at(front: I) -> Self
+    where
+        I: Idx,
+    {
+        Self {
+            data: VecDeque::new(),
+            base: front.into_usize(),
+            _phantom: PhantomData,
+        }
+    }
+    fn offset<X: IdxCompat<I>>(&self, index: X) -> Option<usize> {
+        index.into_usize().checked_sub(self.base)
+    }
+    /// Returns `None` if the element was already popped or never pushed.
+    pub fn get<X>(&self, index: X) -> Option<&T>
+    where
+        X: IdxCompat<I>,
+    {
+        self.data.get(self.offset(index)?)
+    }
+    /// Returns `None` if the element was already popped or never pushed.
+    pub fn get_mut<X>(&mut self, index: X) -> Option<&mut T>
+    where
+        X: IdxCompat<I>,
+    {
+        let offset = self.offset(index)?;
+        self.data.get_mut(offset)
+    }
+    pub fn contains_idx<X>(&self, index: X) -> bool
+    where
+        X: IdxCompat<I>,
+    {
+        self.offset(index).is_some_and(|i| i < self.data.len())
+    }
+    /// The index of the front element, or the index the next pushed element
+    /// will receive if the queue is empty.
+    pub fn front_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.base)
+    }
+    pub fn back_idx(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(I::from_usize(self.base + self.data.len() - 1))
+    }
+    /// The index the next element pushed to the back will receive.
+    pub fn end_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.base + self.data.len())
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn front(&self) -> Option<&T> {
+        self.data.front()
+    }
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.data.front_mut()
+    }
+    pub fn back(&self) -> Option<&T> {
+        self.data.back()
+    }
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.data.back_mut()
+    }
+    pub fn push_back(&mut self, v: T) -> I
+    where
+        I: Idx,
+    {
+        let idx = self.end_idx();
+        self.data.push_back(v);
+        idx
+    }
+    pub fn pop_front(&mut self) -> Option<T> {
+        let v = self.data.pop_front()?;
+        self.base += 1;
+        Some(v)
+    }
+    /// Like [`pop_front`](Self::pop_front), but also returns the index of the
+    /// popped element.
+    pub fn pop_front_enumerated(&mut self) -> Option<(I, T)>
+    where
+        I: Idx,
+    {
+        let idx = self.front_idx();
+        Some((idx, self.pop_front()?))
+    }
+    /// The index of the popped element will be handed out again by the next
+    /// [`push_back`](Self::push_back).
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.data.pop_back()
+    }
+    /// Removes all elements before `front`.
+    ///
+    /// If `front` lies past the back of the queue, the queue is emptied and
+    /// continues at `front`.
+    pub fn truncate_front(&mut self, front: I)
+    where
+        I: Idx,
+    {
+        let front = front.into_usize();
+        let Some(count) = front.checked_sub(self.base) else {
+            return;
+        };
+        if count >= self.data.len() {
+            self.data.clear();
+        } else {
+            drop(self.data.drain(..count));
+        }
+        self.base = front;
+    }
+    /// Removes all elements at and after `end`.
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data
+            .truncate(end.into_usize().saturating_sub(self.base));
+    }
+    /// Removes all elements. Their indices are not handed out again.
+    pub fn clear(&mut self) {
+        self.base += self.data.len();
+        self.data.clear();
+    }
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.data.iter()
+    }
+    pub fn iter_mut(&mut self) -> vec_deque::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, vec_deque::Iter<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(self.front_idx(), &self.data)
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, vec_deque::IterMut<'_, T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(self.front_idx(), &mut self.data)
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, vec_deque::IntoIter<T>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(self.front_idx(), self.data)
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(self.front_idx()..self.end_idx())
+    }
+    pub fn as_vec_deque(&self) -> &VecDeque<T> {
+        &self.data
+    }
+    pub fn as_mut_vec_deque(&mut self) -> &mut VecDeque<T> {
+        &mut self.data
+    }
+    pub fn into_vec_deque(self) -> VecDeque<T> {
+        self.data
+    }
+}
+
+impl<I, T: Clone> Clone for IndexSeqQueue<I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            base: self.base,
+            _phantom: PhantomData,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.base = source.base;
+    }
+}
+
+impl<I, T: Debug> Debug for IndexSeqQueue<I, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IndexSeqQueue")
+            .field("front", &self.base)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl<I, T> Default for IndexSeqQueue<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T> Extend<T> for IndexSeqQueue<I, T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I, T: 'a + Copy> Extend<&'a T> for IndexSeqQueue<I, T> {
+    fn extend<It: IntoIterator<Item = &'a T>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<I, T> FromIterator<T> for IndexSeqQueue<I, T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::from(VecDeque::from_iter(iter))
+    }
+}
+
+impl<I, T> From<VecDeque<T>> for IndexSeqQueue<I, T> {
+    fn from(value: VecDeque<T>) -> Self {
+        Self {
+            data: value,
+            base: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, T> From<IndexSeqQueue<I, T>> for VecDeque<T> {
+    fn from(value: IndexSeqQueue<I, T>) -> Self {
+        value.data
+    }
+}
+
+impl<I, X, T> Index<X> for IndexSeqQueue<I, T>
+where
+    X: IdxCompat<I>,
+{
+    type Output = T;
+    #[inline]
+    fn index(&self, index: X) -> &Self::Output {
+        self.get(index).expect("IndexSeqQueue index not present")
+    }
+}
+
+impl<I, X, T> IndexMut<X> for IndexSeqQueue<I, T>
+where
+    X: IdxCompat<I>,
+{
+    #[inline]
+    fn index_mut(&mut self, index: X) -> &mut Self::Output {
+        self.get_mut(index)
+            .expect("IndexSeqQueue index not present")
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a IndexSeqQueue<I, T> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a mut IndexSeqQueue<I, T> {
+    type Item = &'a mut T;
+    type IntoIter = vec_deque::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<I, T> IntoIterator for IndexSeqQueue<I, T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+/// Two queues are equal if they hold equal elements at the same indices.
+impl<I, T, U> PartialEq<IndexSeqQueue<I, U>> for IndexSeqQueue<I, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &IndexSeqQueue<I, U>) -> bool {
+        self.base == other.base
+            && self.data.len() == other.data.len()
+            && self.data.iter().zip(&other.data).all(|(a, b)| a == b)
+    }
+}
+
+impl<I, T: Eq> Eq for IndexSeqQueue<I, T> {}
+
+impl<I, T: Hash> Hash for IndexSeqQueue<I, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.base.hash(state);
+        self.data.hash(state);
+    }
+}
+
+/// Serialized as a `(front, elements)` tuple so indices survive a roundtrip.
+#[cfg(feature = "serde")]
+impl<I, T> Serialize for IndexSeqQueue<I, T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.base, &self.data).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, T> Deserialize<'de> for IndexSeqQueue<I, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (base, data) = <(usize, VecDeque<T>)>::deserialize(deserializer)?;
+        Ok(Self {
+            data,
+            base,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexSeqQueue;
+
+    #[test]
+    fn indices_survive_pop_front() {
+        let mut q = IndexSeqQueue::<u32, char>::new();
+        q.extend(['a', 'b', 'c']);
+        let d = q.push_back('d');
+        assert_eq!(d, 3);
+        assert_eq!(q.pop_front_enumerated(), Some((0, 'a')));
+        q.truncate_front(2);
+        assert_eq!(q.get(1), None);
+        assert_eq!(q[d], 'd');
+        assert!(q.iter_enumerated().eq([(2, &'c'), (3, &'d')]));
+        assert_eq!((q.front_idx(), q.back_idx()), (2, Some(3)));
+
+        q.clear();
+        assert_eq!(q.push_back('e'), 4);
+        q.truncate_front(10);
+        assert!(q.is_empty());
+        assert_eq!(q.push_back('f'), 10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let mut q = IndexSeqQueue::<u32, i32>::from_iter([1, 2, 3]);
+        assert_eq!(q.pop_front(), Some(1));
+        let s = serde_json::to_string(&q).unwrap();
+        assert_eq!(s, "[1,[2,3]]");
+        assert_eq!(
+            serde_json::from_str::<IndexSeqQueue<u32, i32>>(&s).unwrap(),
+            q
+        );
+    }
+}
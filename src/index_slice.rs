@@ -1,6 +1,6 @@
 use super::Idx;
 use crate::{
-    IdxCompat, IndexArray, IndexRange, IndexRangeBounds,
+    IdxCompat, IdxDelta, IndexArray, IndexRange, IndexRangeBounds,
     index_enumerate::IndexEnumerate,
     sequence::{Sequence, SequenceIndex, SequenceMut, UnsafeSequence, UnsafeSequenceMut},
 };
@@ -97,6 +97,21 @@ impl<I, T> IndexSlice<I, T> {
     {
         self.len().checked_sub(1).map(I::from_usize)
     }
+    /// Const alternative to [`get`](Self::get), taking the index as `usize`.
+    ///
+    /// Use together with the `into_usize_const` generated by
+    /// `#[indexland(extra(Const))]`.
+    pub const fn get_const(&self, index: usize) -> Option<&T> {
+        if index < self.data.len() {
+            Some(&self.data[index])
+        } else {
+            None
+        }
+    }
+    /// Const alternative to indexing, taking the index as `usize`.
+    pub const fn index_const(&self, index: usize) -> &T {
+        &self.data[index]
+    }
     pub const fn first(&self) -> Option<&T> {
         self.data.first()
     }
@@ -213,6 +228,22 @@ impl<I, T> IndexSlice<I, T> {
     {
         self.data.get_mut(idx.into_usize())
     }
+    /// The element `delta` away from `idx`, or `None` if that is out of
+    /// bounds.
+    pub fn get_relative<D: IdxDelta>(&self, idx: I, delta: D) -> Option<&T>
+    where
+        I: Idx,
+    {
+        let idx = idx.into_usize().checked_add_signed(delta.into_isize())?;
+        self.data.get(idx)
+    }
+    pub fn get_relative_mut<D: IdxDelta>(&mut self, idx: I, delta: D) -> Option<&mut T>
+    where
+        I: Idx,
+    {
+        let idx = idx.into_usize().checked_add_signed(delta.into_isize())?;
+        self.data.get_mut(idx)
+    }
 
     /// # Safety
     ///
@@ -635,6 +666,19 @@ impl<I, T> IndexSlice<I, T> {
         self.data.rotate_right(k.into_usize());
     }
 
+    /// Rotates left by `delta`, or right if `delta` is negative.
+    ///
+    /// # Panics
+    /// Panics if `delta` is larger than the length of the slice.
+    pub fn rotate_left_by<D: IdxDelta>(&mut self, delta: D) {
+        let delta = delta.into_isize();
+        if delta >= 0 {
+            self.data.rotate_left(delta.unsigned_abs());
+        } else {
+            self.data.rotate_right(delta.unsigned_abs());
+        }
+    }
+
     pub fn fill(&mut self, value: T)
     where
         T: Clone,
@@ -747,7 +791,7 @@ impl<I, T> IndexSlice<I, T> {
         I: Idx,
         SCI: SequenceIndex<I, IndexSlice<I, T>> + GetDisjointMutIndex<I>,
     {
-        let slice = self as *mut IndexSlice<I, T>;
+        let slice = core::ptr::from_mut(self);
         let mut arr: core::mem::MaybeUninit<[&mut SCI::Output; N]> =
             core::mem::MaybeUninit::uninit();
         let arr_ptr = arr.as_mut_ptr();
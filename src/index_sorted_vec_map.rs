@@ -0,0 +1,1004 @@
+//! A flat map that keeps its entries sorted by key.
+//!
+//! [`IndexSortedVecMap`] stores its entries in a `Vec<(K, V)>` sorted by key
+//! and looks them up using binary search. For small, read-mostly maps this
+//! is usually faster and more compact than hashing. Like
+//! [`IndexHashMap`](crate::IndexHashMap), the positions of the entries are
+//! typed as `I`, and the API mirrors it where possible.
+//!
+//! Note that inserting or removing an entry shifts the positions of all
+//! entries with larger keys. Removal is therefore only offered with the
+//! `shift_*` semantics of [`IndexHashMap`](crate::IndexHashMap), and
+//! methods that would break the key order (`swap_remove*`, `swap_indices`,
+//! `move_index`) are not provided.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexSortedVecMap};
+//!
+//! #[derive(Idx)]
+//! struct SymbolId(u32);
+//!
+//! let mut symbols: IndexSortedVecMap<SymbolId, &str, u32> =
+//!     [("main", 0), ("alloc", 1), ("free", 2), ("main", 3)]
+//!         .into_iter()
+//!         .collect();
+//!
+//! assert_eq!(symbols.len(), 3);
+//! assert_eq!(symbols.get_full("free"), Some((SymbolId(1), &"free", &2)));
+//! assert_eq!(symbols["main"], 3);
+//!
+//! assert_eq!(symbols.insert_full("exit", 4), (SymbolId(1), None));
+//! *symbols.entry("free").or_insert(0) += 10;
+//! assert_eq!(symbols.shift_remove_full("free"), Some((SymbolId(2), "free", 12)));
+//! ```
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ops::{Bound, Index, RangeBounds},
+};
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Idx, IndexRange, IndexRangeBounds, index_enumerate::IndexEnumerate};
+
+pub type Iter<'a, K, V> =
+    core::iter::Map<core::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+
+pub type IterMut<'a, K, V> =
+    core::iter::Map<core::slice::IterMut<'a, (K, V)>, fn(&'a mut (K, V)) -> (&'a K, &'a mut V)>;
+
+pub type IntoIter<K, V> = alloc::vec::IntoIter<(K, V)>;
+
+pub type Keys<'a, K, V> = core::iter::Map<core::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> &'a K>;
+
+pub type Values<'a, K, V> = core::iter::Map<core::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> &'a V>;
+
+pub type ValuesMut<'a, K, V> =
+    core::iter::Map<core::slice::IterMut<'a, (K, V)>, fn(&'a mut (K, V)) -> &'a mut V>;
+
+pub type IntoKeys<K, V> = core::iter::Map<alloc::vec::IntoIter<(K, V)>, fn((K, V)) -> K>;
+
+pub type IntoValues<K, V> = core::iter::Map<alloc::vec::IntoIter<(K, V)>, fn((K, V)) -> V>;
+
+fn entry_ref<K, V>((k, v): &(K, V)) -> (&K, &V) {
+    (k, v)
+}
+
+fn entry_mut<K, V>((k, v): &mut (K, V)) -> (&K, &mut V) {
+    (k, v)
+}
+
+fn key_ref<K, V>((k, _): &(K, V)) -> &K {
+    k
+}
+
+fn value_ref<K, V>((_, v): &(K, V)) -> &V {
+    v
+}
+
+fn value_mut<K, V>((_, v): &mut (K, V)) -> &mut V {
+    v
+}
+
+fn into_key<K, V>((k, _): (K, V)) -> K {
+    k
+}
+
+fn into_value<K, V>((_, v): (K, V)) -> V {
+    v
+}
+
+pub struct IndexSortedVecMap<I, K, V> {
+    data: Vec<(K, V)>,
+    _phantom: PhantomData<fn(I) -> (K, V)>,
+}
+
+impl<I, K, V> IndexSortedVecMap<I, K, V> {
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(cap),
+            _phantom: PhantomData,
+        }
+    }
+    /// Builds a map from entries that are already sorted by key and contain
+    /// no duplicates. Returns `None` if that is not the case.
+    pub fn from_sorted_vec(data: Vec<(K, V)>) -> Option<Self>
+    where
+        K: Ord,
+    {
+        if !data.is_sorted_by(|(a, _), (b, _)| a < b) {
+            return None;
+        }
+        Some(Self {
+            data,
+            _phantom: PhantomData,
+        })
+    }
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.data.len())
+    }
+    pub fn last_idx(&self) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.len().checked_sub(1).map(I::from_usize)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.data.iter().map(entry_ref as fn(_) -> _)
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.data.iter_mut().map(entry_mut as fn(_) -> _)
+    }
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, Iter<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.iter())
+    }
+    pub fn iter_enumerated_mut(&mut self) -> IndexEnumerate<I, IterMut<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.iter_mut())
+    }
+    pub fn iter_enumerated_range<R>(&self, range: R) -> IndexEnumerate<I, Iter<'_, K, V>>
+    where
+        I: Idx,
+        R: IndexRangeBounds<I>,
+    {
+        let range = range.canonicalize(self.len());
+        IndexEnumerate::new(
+            I::from_usize(range.start),
+            self.data[range].iter().map(entry_ref as fn(_) -> _),
+        )
+    }
+    pub fn iter_enumerated_range_mut<R>(&mut self, range: R) -> IndexEnumerate<I, IterMut<'_, K, V>>
+    where
+        I: Idx,
+        R: IndexRangeBounds<I>,
+    {
+        let range = range.canonicalize(self.len());
+        IndexEnumerate::new(
+            I::from_usize(range.start),
+            self.data[range].iter_mut().map(entry_mut as fn(_) -> _),
+        )
+    }
+    pub fn into_iter_enumerated(self) -> IndexEnumerate<I, IntoIter<K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.data)
+    }
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.data.iter().map(key_ref as fn(_) -> _)
+    }
+    pub fn keys_enumerated(&self) -> IndexEnumerate<I, Keys<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.keys())
+    }
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        self.data.into_iter().map(into_key as fn(_) -> _)
+    }
+    pub fn into_keys_enumerated(self) -> IndexEnumerate<I, IntoKeys<K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.into_keys())
+    }
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.data.iter().map(value_ref as fn(_) -> _)
+    }
+    pub fn values_enumerated(&self) -> IndexEnumerate<I, Values<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.values())
+    }
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.data.iter_mut().map(value_mut as fn(_) -> _)
+    }
+    pub fn values_mut_enumerated(&mut self) -> IndexEnumerate<I, ValuesMut<'_, K, V>>
+    where
+        I: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.values_mut())
+    }
+    pub fn into_values(self) -> IntoValues<K, V> {
+        self.data.into_iter().map(into_value as fn(_) -> _)
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn truncate_len(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    pub fn drain<R: IndexRangeBounds<I>>(&mut self, range: R) -> alloc::vec::Drain<'_, (K, V)> {
+        let range = range.canonicalize(self.len());
+        self.data.drain(range)
+    }
+    pub fn drain_len<R: RangeBounds<usize>>(&mut self, range: R) -> alloc::vec::Drain<'_, (K, V)> {
+        self.data.drain(range)
+    }
+    pub fn split_off(&mut self, at: I) -> Self
+    where
+        I: Idx,
+    {
+        self.split_off_len(at.into_usize())
+    }
+    pub fn split_off_len(&mut self, at: usize) -> Self {
+        Self {
+            data: self.data.split_off(at),
+            _phantom: PhantomData,
+        }
+    }
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.data.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    /// Inserts a key-value pair, replacing the value of an existing entry.
+    /// Returns the previous value.
+    ///
+    /// See [`insert_full`](Self::insert_full) to also get the index.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        match self.search(&key) {
+            Ok(i) => Some(core::mem::replace(&mut self.data[i].1, value)),
+            Err(i) => {
+                self.data.insert(i, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Inserts a key-value pair, replacing the value of an existing entry.
+    /// Returns the index of the entry and the previous value.
+    pub fn insert_full(&mut self, key: K, value: V) -> (I, Option<V>)
+    where
+        I: Idx,
+        K: Ord,
+    {
+        match self.search(&key) {
+            Ok(i) => (
+                I::from_usize(i),
+                Some(core::mem::replace(&mut self.data[i].1, value)),
+            ),
+            Err(i) => {
+                self.data.insert(i, (key, value));
+                (I::from_usize(i), None)
+            }
+        }
+    }
+
+    /// Gets the entry for `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, I, K, V>
+    where
+        K: Ord,
+    {
+        match self.search(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry {
+                data: &mut self.data,
+                index,
+                _phantom: PhantomData,
+            }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                data: &mut self.data,
+                index,
+                key,
+                _phantom: PhantomData,
+            }),
+        }
+    }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.shift_remove_entry(key).map(into_value)
+    }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    /// Returns its key and its value.
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let i = self.search(key).ok()?;
+        Some(self.data.remove(i))
+    }
+
+    /// Removes the entry for `key`, shifting all following entries.
+    /// Returns the index the entry had, its key and its value.
+    pub fn shift_remove_full<Q>(&mut self, key: &Q) -> Option<(I, K, V)>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let i = self.search(key).ok()?;
+        let (k, v) = self.data.remove(i);
+        Some((I::from_usize(i), k, v))
+    }
+
+    /// Removes the entry at `index`, shifting all following entries.
+    pub fn shift_remove_index(&mut self, index: I) -> Option<(K, V)>
+    where
+        I: Idx,
+    {
+        let index = index.into_usize();
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.data.remove(index))
+    }
+
+    pub fn get_index(&self, i: I) -> Option<(&K, &V)>
+    where
+        I: Idx,
+    {
+        self.data.get(i.into_usize()).map(entry_ref)
+    }
+
+    pub fn get_index_mut(&mut self, i: I) -> Option<(&K, &mut V)>
+    where
+        I: Idx,
+    {
+        self.data.get_mut(i.into_usize()).map(entry_mut)
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.data.first().map(entry_ref)
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.data.last().map(entry_ref)
+    }
+
+    pub fn as_slice(&self) -> &[(K, V)] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<(K, V)> {
+        self.data
+    }
+
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let i = self.search(key).ok()?;
+        Some(&self.data[i].1)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let i = self.search(key).ok()?;
+        Some(&mut self.data[i].1)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Return item index, if it exists in the map
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<I>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).ok().map(I::from_usize)
+    }
+
+    /// Return item index, key and value
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(I, &K, &V)>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let i = self.search(key).ok()?;
+        let (k, v) = &self.data[i];
+        Some((I::from_usize(i), k, v))
+    }
+
+    /// The index range of all entries whose keys lie within `range`.
+    pub fn range_indices<Q, R>(&self, range: R) -> IndexRange<I>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(q) => self.data.partition_point(|(k, _)| k.borrow() < q),
+            Bound::Excluded(q) => self.data.partition_point(|(k, _)| k.borrow() <= q),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(q) => self.data.partition_point(|(k, _)| k.borrow() <= q),
+            Bound::Excluded(q) => self.data.partition_point(|(k, _)| k.borrow() < q),
+            Bound::Unbounded => self.len(),
+        };
+        IndexRange::new(I::from_usize(start)..I::from_usize(end.max(start)))
+    }
+
+    /// Iterates over all entries whose keys lie within `range`,
+    /// together with their indices.
+    pub fn range<Q, R>(&self, range: R) -> IndexEnumerate<I, Iter<'_, K, V>>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.iter_enumerated_range(self.range_indices(range))
+    }
+
+    /// Iterates over all entries whose keys lie within `range`,
+    /// together with their indices.
+    pub fn range_mut<Q, R>(&mut self, range: R) -> IndexEnumerate<I, IterMut<'_, K, V>>
+    where
+        I: Idx,
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.iter_enumerated_range_mut(self.range_indices(range))
+    }
+
+    /// Restores the map invariants after appending unsorted entries starting
+    /// at `start`. For duplicate keys the value inserted last wins.
+    fn sort_and_dedup(&mut self, start: usize)
+    where
+        K: Ord,
+    {
+        if self.data[start..].is_empty()
+            || (self.data[start.saturating_sub(1)..].is_sorted_by(|(a, _), (b, _)| a < b))
+        {
+            return;
+        }
+        // stable, so later duplicates stay behind earlier ones
+        self.data.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.data.dedup_by(|later, earlier| {
+            if later.0 != earlier.0 {
+                return false;
+            }
+            core::mem::swap(&mut later.1, &mut earlier.1);
+            true
+        });
+    }
+}
+
+impl<I, K, V> Default for IndexSortedVecMap<I, K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, K: Clone, V: Clone> Clone for IndexSortedVecMap<I, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, K: Debug, V: Debug> Debug for IndexSortedVecMap<I, K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<I, K: Ord, V> Extend<(K, V)> for IndexSortedVecMap<I, K, V> {
+    fn extend<It: IntoIterator<Item = (K, V)>>(&mut self, iter: It) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        self.sort_and_dedup(start);
+    }
+}
+
+impl<'a, I, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for IndexSortedVecMap<I, K, V> {
+    fn extend<It: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: It) {
+        self.extend(iter.into_iter().map(|(k, v)| (*k, *v)));
+    }
+}
+
+impl<I, K: Ord, V> FromIterator<(K, V)> for IndexSortedVecMap<I, K, V> {
+    fn from_iter<It: IntoIterator<Item = (K, V)>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<I, K: Ord, V> From<Vec<(K, V)>> for IndexSortedVecMap<I, K, V> {
+    fn from(data: Vec<(K, V)>) -> Self {
+        let mut res = Self {
+            data,
+            _phantom: PhantomData,
+        };
+        res.sort_and_dedup(0);
+        res
+    }
+}
+
+impl<I, K: Ord, V, const N: usize> From<[(K, V); N]> for IndexSortedVecMap<I, K, V> {
+    fn from(arr: [(K, V); N]) -> Self {
+        Self::from_iter(arr)
+    }
+}
+
+impl<I, K, V> IntoIterator for IndexSortedVecMap<I, K, V> {
+    type Item = (K, V);
+
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, I, K, V> IntoIterator for &'a IndexSortedVecMap<I, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, I, K, V> IntoIterator for &'a mut IndexSortedVecMap<I, K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<I, K, V, Q> Index<&Q> for IndexSortedVecMap<I, K, V>
+where
+    K: Borrow<Q>,
+    Q: ?Sized + Ord,
+{
+    type Output = V;
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in IndexSortedVecMap")
+    }
+}
+
+impl<K, I1, V1, I2, V2> PartialEq<IndexSortedVecMap<I2, K, V2>> for IndexSortedVecMap<I1, K, V1>
+where
+    K: PartialEq,
+    V1: PartialEq<V2>,
+{
+    fn eq(&self, other: &IndexSortedVecMap<I2, K, V2>) -> bool {
+        self.len() == other.len()
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|((k1, v1), (k2, v2))| k1 == k2 && v1 == v2)
+    }
+}
+
+impl<I, K: Eq, V: Eq> Eq for IndexSortedVecMap<I, K, V> {}
+
+#[cfg(feature = "serde")]
+impl<I, K, V> Serialize for IndexSortedVecMap<I, K, V>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, K, V> Deserialize<'de> for IndexSortedVecMap<I, K, V>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<I, K, V>(PhantomData<fn() -> IndexSortedVecMap<I, K, V>>);
+
+        impl<'de, I, K, V> serde::de::Visitor<'de> for MapVisitor<I, K, V>
+        where
+            K: Deserialize<'de> + Ord,
+            V: Deserialize<'de>,
+        {
+            type Value = IndexSortedVecMap<I, K, V>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut data = Vec::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+                while let Some(entry) = map.next_entry()? {
+                    data.push(entry);
+                }
+                Ok(IndexSortedVecMap::from(data))
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+// ========== Entry ==========
+
+/// Entry for an existing key-value pair in an [`IndexSortedVecMap`]
+/// or a vacant location to insert one.
+pub enum Entry<'a, I, K, V> {
+    /// Existing slot with equal key.
+    Occupied(OccupiedEntry<'a, I, K, V>),
+    /// Vacant slot (no equal key in the map).
+    Vacant(VacantEntry<'a, I, K, V>),
+}
+
+impl<'a, I, K, V> Entry<'a, I, K, V> {
+    /// Return the index where the key-value pair exists or will be inserted.
+    pub fn index(&self) -> I
+    where
+        I: Idx,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.index(),
+            Entry::Vacant(entry) => entry.index(),
+        }
+    }
+
+    /// Sets the value of the entry (after inserting if vacant), and returns an `OccupiedEntry`.
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, I, K, V> {
+        match self {
+            Entry::Occupied(mut entry) => {
+                _ = entry.insert(value);
+                entry
+            }
+            Entry::Vacant(entry) => entry.insert_entry(value),
+        }
+    }
+
+    /// Inserts the given default value in the entry if it is vacant and returns a mutable
+    /// reference to it. Otherwise a mutable reference to an already existent value is returned.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of the `call` function in the entry if it is vacant and returns a mutable
+    /// reference to it. Otherwise a mutable reference to an already existent value is returned.
+    pub fn or_insert_with<F>(self, call: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(call()),
+        }
+    }
+
+    /// Inserts the result of the `call` function with a reference to the entry's key if it is
+    /// vacant, and returns a mutable reference to the new value. Otherwise a mutable reference to
+    /// an already existent value is returned.
+    pub fn or_insert_with_key<F>(self, call: F) -> &'a mut V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = call(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Gets a reference to the entry's key, either within the map if occupied,
+    /// or else the new key that was used to find the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Modifies the entry if it is occupied.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+
+    /// Inserts a default-constructed value in the entry if it is vacant and returns a mutable
+    /// reference to it. Otherwise a mutable reference to an already existent value is returned.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+}
+
+impl<I, K: fmt::Debug, V: fmt::Debug> fmt::Debug for Entry<'_, I, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Entry");
+        match self {
+            Entry::Vacant(v) => tuple.field(v),
+            Entry::Occupied(o) => tuple.field(o),
+        }
+        .finish()
+    }
+}
+
+/// A view into an occupied entry in an [`IndexSortedVecMap`].
+/// It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, I, K, V> {
+    data: &'a mut Vec<(K, V)>,
+    index: usize,
+    _phantom: PhantomData<I>,
+}
+
+impl<'a, I, K, V> OccupiedEntry<'a, I, K, V> {
+    /// Return the index of the key-value pair
+    pub fn index(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.index)
+    }
+
+    /// Gets a reference to the entry's key in the map.
+    pub fn key(&self) -> &K {
+        &self.data[self.index].0
+    }
+
+    /// Gets a reference to the entry's value in the map.
+    pub fn get(&self) -> &V {
+        &self.data[self.index].1
+    }
+
+    /// Gets a mutable reference to the entry's value in the map.
+    ///
+    /// If you need a reference which may outlive the destruction of the
+    /// [`Entry`] value, see [`into_mut`][Self::into_mut].
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.data[self.index].1
+    }
+
+    /// Converts into a mutable reference to the entry's value in the map,
+    /// with a lifetime bound to the map itself.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.data[self.index].1
+    }
+
+    /// Sets the value of the entry to `value`, and returns the entry's old value.
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the key, value pair stored in the map for this entry, and return the value.
+    ///
+    /// All following entries are shifted down by one.
+    pub fn shift_remove(self) -> V {
+        self.shift_remove_entry().1
+    }
+
+    /// Remove and return the key, value pair stored in the map for this entry.
+    ///
+    /// All following entries are shifted down by one.
+    pub fn shift_remove_entry(self) -> (K, V) {
+        self.data.remove(self.index)
+    }
+}
+
+impl<I, K: fmt::Debug, V: fmt::Debug> fmt::Debug for OccupiedEntry<'_, I, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+/// A view into a vacant entry in an [`IndexSortedVecMap`].
+/// It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, I, K, V> {
+    data: &'a mut Vec<(K, V)>,
+    index: usize,
+    key: K,
+    _phantom: PhantomData<I>,
+}
+
+impl<'a, I, K, V> VacantEntry<'a, I, K, V> {
+    /// Return the index where the key-value pair will be inserted
+    /// to keep the keys sorted.
+    pub fn index(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.index)
+    }
+
+    /// Gets a reference to the key that was used to find the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key, leaving the entry vacant.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the entry's key and the given value into the map, and returns a mutable reference
+    /// to the value. All following entries are shifted up by one.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.insert_entry(value).into_mut()
+    }
+
+    /// Inserts the entry's key and the given value into the map, and returns an `OccupiedEntry`.
+    /// All following entries are shifted up by one.
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, I, K, V> {
+        self.data.insert(self.index, (self.key, value));
+        OccupiedEntry {
+            data: self.data,
+            index: self.index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, K: fmt::Debug, V> fmt::Debug for VacantEntry<'_, I, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(self.key()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexSortedVecMap;
+    use crate::IndexRange;
+
+    #[test]
+    fn bulk_construction_keeps_last_duplicate() {
+        let map: IndexSortedVecMap<u32, i32, &str> =
+            [(3, "a"), (1, "b"), (3, "c"), (2, "d"), (1, "e")]
+                .into_iter()
+                .collect();
+        assert!(map.iter().eq([(&1, &"e"), (&2, &"d"), (&3, &"c")]));
+    }
+
+    #[test]
+    fn range_yields_indices() {
+        let mut map = (0..10)
+            .map(|i| (i * 10, i))
+            .collect::<IndexSortedVecMap<u32, i32, i32>>();
+        assert!(map.range_indices(15..=40) == IndexRange::new(2..5));
+        assert!(map.range(15..30).eq([(2, (&20, &2))]));
+        assert!(map.range(..=10).eq([(0, (&0, &0)), (1, (&10, &1))]));
+        assert_eq!(map.insert_full(25, -1), (3, None));
+        assert_eq!(map.insert_full(25, -2), (3, Some(-1)));
+        assert_eq!(map.get_index(3), Some((&25, &-2)));
+        assert_eq!(map.shift_remove_full(&0), Some((0, 0, 0)));
+        assert_eq!(map.get_index_of(&25), Some(2));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let map = IndexSortedVecMap::<u32, i32, i32>::from([(2, 20), (1, 10)]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"1":10,"2":20}"#);
+        assert_eq!(
+            serde_json::from_str::<IndexSortedVecMap<u32, i32, i32>>(&json).unwrap(),
+            map
+        );
+    }
+
+    #[cfg(all(feature = "indexmap", feature = "std"))]
+    #[test]
+    fn interchangeable_with_index_hash_map() {
+        macro_rules! exercise {
+            ($map:ty) => {{
+                let mut map = <$map>::new();
+                for k in [30, 10, 20] {
+                    *map.entry(k).or_insert(0) += k;
+                }
+                *map.entry(10).or_default() += 1;
+                assert_eq!(map.insert_full(40, 4).1, None);
+                assert_eq!(map.get(&10), Some(&11));
+                assert_eq!(map.shift_remove(&40), Some(4));
+                let i = map.get_index_of(&20).unwrap();
+                assert_eq!(map.shift_remove_full(&20), Some((i, 20, 20)));
+                assert_eq!(map.shift_remove_entry(&30), Some((30, 30)));
+                let tail = map.split_off_len(1);
+                assert!(tail.is_empty());
+                assert_eq!(
+                    map.drain_len(..).collect::<alloc::vec::Vec<_>>(),
+                    [(10, 11)]
+                );
+            }};
+        }
+        exercise!(IndexSortedVecMap<u32, i32, i32>);
+        exercise!(crate::IndexHashMap<u32, i32, i32>);
+    }
+}
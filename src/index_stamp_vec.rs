@@ -0,0 +1,431 @@
+//! A sparse table over a dense index space that can be cleared in O(1).
+//!
+//! Every slot of an [`IndexStampVec`] carries the epoch in which it was last
+//! written. [`IndexStampVec::clear`] simply bumps the current epoch, which
+//! turns all previously written slots stale without touching them.
+//!
+//! This is mainly useful for scratch tables like `visited` sets in graph
+//! searches that are reused across many queries, where refilling the whole
+//! table would otherwise dominate the runtime.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexStampVec};
+//!
+//! #[derive(Idx)]
+//! struct NodeId(u32);
+//!
+//! let mut visited = IndexStampVec::<NodeId, bool>::with_len(1024);
+//!
+//! visited.insert(NodeId(3), true);
+//! assert_eq!(visited.get(NodeId(3)), Some(&true));
+//!
+//! visited.clear(); // O(1)
+//! assert_eq!(visited.get(NodeId(3)), None);
+//! ```
+
+use core::{fmt::Debug, iter::FusedIterator, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+use crate::Idx;
+
+/// Stamp value of slots that were never written or explicitly removed.
+/// The epoch of an [`IndexStampVec`] never takes this value.
+const STALE_STAMP: u32 = 0;
+
+struct Slot<T> {
+    stamp: u32,
+    // Stale slots may still hold values from earlier epochs. These are dropped
+    // lazily once the slot is overwritten, or eagerly by `reset`.
+    value: Option<T>,
+}
+
+impl<T> Slot<T> {
+    const fn vacant() -> Self {
+        Self {
+            stamp: STALE_STAMP,
+            value: None,
+        }
+    }
+}
+
+impl<T: Clone> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            stamp: self.stamp,
+            value: self.value.clone(),
+        }
+    }
+}
+
+pub struct IndexStampVec<I, T> {
+    slots: Vec<Slot<T>>,
+    epoch: u32,
+    _phantom: PhantomData<fn(I) -> T>,
+}
+
+impl<I, T> IndexStampVec<I, T> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            epoch: STALE_STAMP + 1,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(cap),
+            epoch: STALE_STAMP + 1,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a table with `len` empty slots.
+    pub fn with_len(len: usize) -> Self {
+        let mut res = Self::with_capacity(len);
+        res.slots.resize_with(len, Slot::vacant);
+        res
+    }
+
+    /// The number of slots, including empty ones.
+    /// Indices `>= len` are implicitly empty.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.slots.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.slots.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.slots.shrink_to_fit();
+    }
+
+    /// Grows or shrinks the table to `len` slots. New slots are empty.
+    pub fn resize(&mut self, len: usize) {
+        self.slots.resize_with(len, Slot::vacant);
+    }
+
+    /// Empties all slots in O(1) by advancing the epoch.
+    ///
+    /// Values from previous epochs are not dropped until their slot is
+    /// overwritten. Use [`reset`](Self::reset) to drop them eagerly.
+    ///
+    /// Once every `u32::MAX` calls, the epoch wraps around and all stamps
+    /// have to be reset, which makes that particular call O(n).
+    pub fn clear(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+        if self.epoch == STALE_STAMP {
+            for slot in &mut self.slots {
+                slot.stamp = STALE_STAMP;
+            }
+            self.epoch = STALE_STAMP + 1;
+        }
+    }
+
+    /// Empties all slots and drops all values, including stale ones. O(n).
+    pub fn reset(&mut self) {
+        for slot in &mut self.slots {
+            *slot = Slot::vacant();
+        }
+        self.epoch = STALE_STAMP + 1;
+    }
+
+    pub fn contains(&self, idx: I) -> bool
+    where
+        I: Idx,
+    {
+        self.get(idx).is_some()
+    }
+
+    pub fn get(&self, idx: I) -> Option<&T>
+    where
+        I: Idx,
+    {
+        let slot = self.slots.get(idx.into_usize())?;
+        if slot.stamp != self.epoch {
+            return None;
+        }
+        slot.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T>
+    where
+        I: Idx,
+    {
+        let slot = self.slots.get_mut(idx.into_usize())?;
+        if slot.stamp != self.epoch {
+            return None;
+        }
+        slot.value.as_mut()
+    }
+
+    fn slot_mut_grow(&mut self, idx: usize) -> &mut Slot<T> {
+        if idx >= self.slots.len() {
+            self.slots.resize_with(idx + 1, Slot::vacant);
+        }
+        &mut self.slots[idx]
+    }
+
+    /// Inserts `value` at `idx`, growing the table if necessary.
+    /// Returns the previous value if the slot was occupied in the current epoch.
+    pub fn insert(&mut self, idx: I, value: T) -> Option<T>
+    where
+        I: Idx,
+    {
+        let epoch = self.epoch;
+        let slot = self.slot_mut_grow(idx.into_usize());
+        let prev = slot.value.replace(value);
+        let live = slot.stamp == epoch;
+        slot.stamp = epoch;
+        if live { prev } else { None }
+    }
+
+    /// Returns the value at `idx`, inserting the result of `f` first if the
+    /// slot is empty. Grows the table if necessary.
+    pub fn get_or_insert_with(&mut self, idx: I, f: impl FnOnce() -> T) -> &mut T
+    where
+        I: Idx,
+    {
+        let epoch = self.epoch;
+        let slot = self.slot_mut_grow(idx.into_usize());
+        if slot.stamp != epoch || slot.value.is_none() {
+            slot.value = Some(f());
+            slot.stamp = epoch;
+        }
+        slot.value.as_mut().unwrap()
+    }
+
+    pub fn remove(&mut self, idx: I) -> Option<T>
+    where
+        I: Idx,
+    {
+        let epoch = self.epoch;
+        let slot = self.slots.get_mut(idx.into_usize())?;
+        if slot.stamp != epoch {
+            return None;
+        }
+        slot.stamp = STALE_STAMP;
+        slot.value.take()
+    }
+
+    /// Iterates over all slots that are occupied in the current epoch.
+    pub fn iter_enumerated(&self) -> IterEnumerated<'_, I, T> {
+        IterEnumerated {
+            base: self.slots.iter().enumerate(),
+            epoch: self.epoch,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over all slots that are occupied in the current epoch.
+    pub fn iter_enumerated_mut(&mut self) -> IterEnumeratedMut<'_, I, T> {
+        IterEnumeratedMut {
+            base: self.slots.iter_mut().enumerate(),
+            epoch: self.epoch,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the indices of all occupied slots.
+    pub fn indices(&self) -> impl Iterator<Item = I>
+    where
+        I: Idx,
+    {
+        self.iter_enumerated().map(|(idx, _)| idx)
+    }
+}
+
+impl<I, T> Default for IndexStampVec<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T> Clone for IndexStampVec<I, T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            epoch: self.epoch,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, T> Debug for IndexStampVec<I, T>
+where
+    I: Idx + Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter_enumerated()).finish()
+    }
+}
+
+impl<I, T> Extend<(I, T)> for IndexStampVec<I, T>
+where
+    I: Idx,
+{
+    fn extend<It: IntoIterator<Item = (I, T)>>(&mut self, iter: It) {
+        for (idx, value) in iter {
+            let _ = self.insert(idx, value);
+        }
+    }
+}
+
+impl<I, T> FromIterator<(I, T)> for IndexStampVec<I, T>
+where
+    I: Idx,
+{
+    fn from_iter<It: IntoIterator<Item = (I, T)>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+// ===== IterEnumerated =====
+pub struct IterEnumerated<'a, I, T> {
+    base: core::iter::Enumerate<core::slice::Iter<'a, Slot<T>>>,
+    epoch: u32,
+    _phantom: PhantomData<fn(I) -> &'a T>,
+}
+
+impl<'a, I, T> Iterator for IterEnumerated<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = (I, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let epoch = self.epoch;
+        self.base.find_map(|(idx, slot)| {
+            if slot.stamp != epoch {
+                return None;
+            }
+            Some((I::from_usize(idx), slot.value.as_ref()?))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.base.size_hint().1)
+    }
+}
+
+impl<I, T> DoubleEndedIterator for IterEnumerated<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let epoch = self.epoch;
+        self.base.by_ref().rev().find_map(|(idx, slot)| {
+            if slot.stamp != epoch {
+                return None;
+            }
+            Some((I::from_usize(idx), slot.value.as_ref()?))
+        })
+    }
+}
+
+impl<I, T> FusedIterator for IterEnumerated<'_, I, T> where I: Idx {}
+
+// ===== IterEnumeratedMut =====
+pub struct IterEnumeratedMut<'a, I, T> {
+    base: core::iter::Enumerate<core::slice::IterMut<'a, Slot<T>>>,
+    epoch: u32,
+    _phantom: PhantomData<fn(I) -> &'a mut T>,
+}
+
+impl<'a, I, T> Iterator for IterEnumeratedMut<'a, I, T>
+where
+    I: Idx,
+{
+    type Item = (I, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let epoch = self.epoch;
+        self.base.find_map(|(idx, slot)| {
+            if slot.stamp != epoch {
+                return None;
+            }
+            Some((I::from_usize(idx), slot.value.as_mut()?))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.base.size_hint().1)
+    }
+}
+
+impl<I, T> DoubleEndedIterator for IterEnumeratedMut<'_, I, T>
+where
+    I: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let epoch = self.epoch;
+        self.base.by_ref().rev().find_map(|(idx, slot)| {
+            if slot.stamp != epoch {
+                return None;
+            }
+            Some((I::from_usize(idx), slot.value.as_mut()?))
+        })
+    }
+}
+
+impl<I, T> FusedIterator for IterEnumeratedMut<'_, I, T> where I: Idx {}
+
+#[cfg(test)]
+mod test {
+    use super::IndexStampVec;
+
+    #[test]
+    fn clear_invalidates() {
+        let mut v = IndexStampVec::<u32, i32>::new();
+        assert_eq!(v.insert(5, 1), None);
+        assert_eq!(v.insert(5, 2), Some(1));
+        assert_eq!(v.len(), 6);
+        v.clear();
+        assert_eq!(v.get(5), None);
+        assert_eq!(v.insert(5, 3), None);
+        assert_eq!(*v.get_or_insert_with(5, || 4), 3);
+        assert_eq!(*v.get_or_insert_with(2, || 4), 4);
+        assert!(v.iter_enumerated().eq([(2, &4), (5, &3)]));
+        assert_eq!(v.remove(2), Some(4));
+        assert_eq!(v.get(2), None);
+    }
+
+    #[test]
+    fn epoch_wraparound() {
+        let mut v = IndexStampVec::<u32, i32>::with_len(3);
+        assert_eq!(v.insert(0, 0), None);
+        v.epoch = u32::MAX;
+        assert_eq!(v.insert(1, 1), None);
+        v.clear();
+        assert_eq!(v.epoch, 1);
+        // the slot written in the very first epoch must not be resurrected
+        assert_eq!(v.get(0), None);
+        assert_eq!(v.get(1), None);
+        assert_eq!(v.insert(2, 2), None);
+        assert!(v.iter_enumerated().eq([(2, &2)]));
+    }
+}
@@ -0,0 +1,474 @@
+//! A [`str`] wrapper addressed by typed byte offsets, and [`Span`], a `Copy`
+//! range of such offsets.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexStr, Span};
+//!
+//! #[derive(Idx)]
+//! struct ByteOffset(u32);
+//!
+//! let src = IndexStr::<ByteOffset>::new("let x = 42;");
+//! let eq = src.find("=").unwrap();
+//! assert_eq!(eq, ByteOffset(6));
+//!
+//! let name = Span::new(ByteOffset(4), ByteOffset(5));
+//! assert_eq!(&src[name], "x");
+//!
+//! let (lhs, rhs) = src.split_at(eq);
+//! assert_eq!((lhs.as_str(), rhs.as_str()), ("let x ", "= 42;"));
+//! ```
+
+use core::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, boxed::Box};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "alloc")]
+use crate::IndexString;
+
+use crate::{IndexRange, IndexRangeBounds};
+
+use super::{idx::Idx, index_slice::IndexSlice};
+
+#[repr(transparent)]
+pub struct IndexStr<I> {
+    _phantom: PhantomData<fn(I) -> u8>,
+    data: str,
+}
+
+impl<I> IndexStr<I> {
+    #[inline(always)]
+    pub const fn new(s: &str) -> &Self {
+        unsafe { &*(core::ptr::from_ref(s) as *const Self) }
+    }
+    #[inline(always)]
+    pub const fn from_mut_str(s: &mut str) -> &mut Self {
+        unsafe { &mut *(core::ptr::from_mut(s) as *mut Self) }
+    }
+    #[cfg(feature = "alloc")]
+    pub fn from_boxed_str(str_box: Box<str>) -> Box<Self> {
+        unsafe { Box::from_raw(Box::into_raw(str_box) as *mut Self) }
+    }
+    #[cfg(feature = "alloc")]
+    pub fn into_boxed_str(self: Box<Self>) -> Box<str> {
+        unsafe { Box::from_raw(Box::into_raw(self) as *mut str) }
+    }
+    #[inline(always)]
+    pub const fn as_str(&self) -> &str {
+        &self.data
+    }
+    #[inline(always)]
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        &mut self.data
+    }
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+    pub const fn as_index_slice(&self) -> &IndexSlice<I, u8> {
+        IndexSlice::from_slice(self.data.as_bytes())
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.data.len())
+    }
+    pub fn indices(&self) -> IndexRange<I>
+    where
+        I: Idx,
+    {
+        IndexRange::new(I::ZERO..self.len_idx())
+    }
+    pub fn is_char_boundary(&self, idx: I) -> bool
+    where
+        I: Idx,
+    {
+        self.data.is_char_boundary(idx.into_usize())
+    }
+
+    /// Returns `None` if the range is out of bounds or does not lie on char
+    /// boundaries.
+    pub fn get<R: IndexRangeBounds<I>>(&self, range: R) -> Option<&IndexStr<I>> {
+        self.data
+            .get(range.canonicalize(self.data.len()))
+            .map(IndexStr::new)
+    }
+    /// Returns `None` if the range is out of bounds or does not lie on char
+    /// boundaries.
+    pub fn get_mut<R: IndexRangeBounds<I>>(&mut self, range: R) -> Option<&mut IndexStr<I>> {
+        let range = range.canonicalize(self.data.len());
+        self.data.get_mut(range).map(IndexStr::from_mut_str)
+    }
+
+    /// Note that the offsets of the second half start at zero again.
+    ///
+    /// # Panics
+    /// Panics if `mid` is out of bounds or not on a char boundary.
+    pub fn split_at(&self, mid: I) -> (&IndexStr<I>, &IndexStr<I>)
+    where
+        I: Idx,
+    {
+        let (a, b) = self.data.split_at(mid.into_usize());
+        (IndexStr::new(a), IndexStr::new(b))
+    }
+    pub fn split_at_checked(&self, mid: I) -> Option<(&IndexStr<I>, &IndexStr<I>)>
+    where
+        I: Idx,
+    {
+        let (a, b) = self.data.split_at_checked(mid.into_usize())?;
+        Some((IndexStr::new(a), IndexStr::new(b)))
+    }
+
+    pub fn find(&self, pat: &str) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.data.find(pat).map(I::from_usize)
+    }
+    pub fn rfind(&self, pat: &str) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.data.rfind(pat).map(I::from_usize)
+    }
+    /// Returns the offset of the first char matching `pred`.
+    pub fn find_char(&self, pred: impl FnMut(char) -> bool) -> Option<I>
+    where
+        I: Idx,
+    {
+        self.data.find(pred).map(I::from_usize)
+    }
+
+    pub fn chars(&self) -> core::str::Chars<'_> {
+        self.data.chars()
+    }
+    pub fn char_indices_enumerated(&self) -> CharIndices<'_, I> {
+        CharIndices {
+            data: self.data.char_indices(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`IndexStr::char_indices_enumerated`].
+#[derive(Clone)]
+pub struct CharIndices<'a, I> {
+    data: core::str::CharIndices<'a>,
+    _phantom: PhantomData<fn() -> I>,
+}
+
+impl<I: Idx> Iterator for CharIndices<'_, I> {
+    type Item = (I, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, c) = self.data.next()?;
+        Some((I::from_usize(i), c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<I: Idx> DoubleEndedIterator for CharIndices<'_, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (i, c) = self.data.next_back()?;
+        Some((I::from_usize(i), c))
+    }
+}
+
+impl<I: Idx> core::iter::FusedIterator for CharIndices<'_, I> {}
+
+impl<I, R: IndexRangeBounds<I>> Index<R> for IndexStr<I> {
+    type Output = IndexStr<I>;
+
+    fn index(&self, index: R) -> &Self::Output {
+        IndexStr::new(&self.data[index.canonicalize(self.data.len())])
+    }
+}
+
+impl<I, R: IndexRangeBounds<I>> IndexMut<R> for IndexStr<I> {
+    fn index_mut(&mut self, index: R) -> &mut Self::Output {
+        let range = index.canonicalize(self.data.len());
+        IndexStr::from_mut_str(&mut self.data[range])
+    }
+}
+
+impl<I> Debug for IndexStr<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I> Display for IndexStr<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.data, f)
+    }
+}
+
+impl<I> Default for &IndexStr<I> {
+    fn default() -> Self {
+        IndexStr::new("")
+    }
+}
+
+impl<I> AsRef<str> for IndexStr<I> {
+    fn as_ref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<I> AsRef<[u8]> for IndexStr<I> {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+impl<I> AsRef<IndexSlice<I, u8>> for IndexStr<I> {
+    fn as_ref(&self) -> &IndexSlice<I, u8> {
+        self.as_index_slice()
+    }
+}
+
+impl<'a, I> From<&'a str> for &'a IndexStr<I> {
+    fn from(value: &'a str) -> Self {
+        IndexStr::new(value)
+    }
+}
+
+impl<'a, I> From<&'a mut str> for &'a mut IndexStr<I> {
+    fn from(value: &'a mut str) -> Self {
+        IndexStr::from_mut_str(value)
+    }
+}
+
+impl<'a, I> From<&'a IndexStr<I>> for &'a str {
+    fn from(value: &'a IndexStr<I>) -> Self {
+        &value.data
+    }
+}
+
+impl<I> PartialEq for IndexStr<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<I> PartialEq<str> for IndexStr<I> {
+    fn eq(&self, other: &str) -> bool {
+        &self.data == other
+    }
+}
+
+impl<I> PartialEq<&str> for IndexStr<I> {
+    fn eq(&self, other: &&str) -> bool {
+        &self.data == *other
+    }
+}
+
+impl<I> PartialEq<IndexStr<I>> for str {
+    fn eq(&self, other: &IndexStr<I>) -> bool {
+        self == &other.data
+    }
+}
+
+impl<I> PartialEq<IndexStr<I>> for &str {
+    fn eq(&self, other: &IndexStr<I>) -> bool {
+        *self == &other.data
+    }
+}
+
+impl<I> Eq for IndexStr<I> {}
+
+impl<I> PartialOrd for IndexStr<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I> Ord for IndexStr<I> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl<I> Hash for IndexStr<I> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> ToOwned for IndexStr<I> {
+    type Owned = IndexString<I>;
+
+    fn to_owned(&self) -> Self::Owned {
+        IndexString::from(&self.data)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I> Serialize for IndexStr<I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.data)
+    }
+}
+
+/// A `Copy` range of offsets, e.g. the location of a token in source text.
+///
+/// Can be used wherever an [`IndexRangeBounds`] is accepted.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span<I> {
+    pub start: I,
+    pub end: I,
+}
+
+impl<I> Span<I> {
+    pub const fn new(start: I, end: I) -> Self {
+        Self { start, end }
+    }
+    pub fn len(&self) -> usize
+    where
+        I: Idx,
+    {
+        self.end
+            .into_usize()
+            .saturating_sub(self.start.into_usize())
+    }
+    pub fn is_empty(&self) -> bool
+    where
+        I: PartialOrd,
+    {
+        self.start >= self.end
+    }
+    pub fn contains(&self, idx: &I) -> bool
+    where
+        I: PartialOrd,
+    {
+        self.start <= *idx && *idx < self.end
+    }
+    /// Returns the smallest span covering both `self` and `other`.
+    pub fn cover(self, other: Self) -> Self
+    where
+        I: Ord,
+    {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+    pub fn index_range(self) -> IndexRange<I> {
+        IndexRange::new(self.start..self.end)
+    }
+}
+
+impl<I: Debug> Debug for Span<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+impl<I> From<Range<I>> for Span<I> {
+    fn from(r: Range<I>) -> Self {
+        Span {
+            start: r.start,
+            end: r.end,
+        }
+    }
+}
+
+impl<I> From<Span<I>> for Range<I> {
+    fn from(s: Span<I>) -> Self {
+        s.start..s.end
+    }
+}
+
+impl<I> From<IndexRange<I>> for Span<I> {
+    fn from(r: IndexRange<I>) -> Self {
+        Span {
+            start: r.start,
+            end: r.end,
+        }
+    }
+}
+
+impl<I> RangeBounds<I> for Span<I> {
+    fn start_bound(&self) -> Bound<&I> {
+        Bound::Included(&self.start)
+    }
+    fn end_bound(&self) -> Bound<&I> {
+        Bound::Excluded(&self.end)
+    }
+}
+
+impl<I: Idx> IndexRangeBounds<I> for Span<I> {
+    type BaseRange = Range<I>;
+    type IndexRange = IndexRange<I>;
+    type UsizeRange = Range<usize>;
+    fn base_range(self) -> Self::BaseRange {
+        Range::from(self)
+    }
+    fn index_range(self) -> Self::IndexRange {
+        IndexRange::new(self.start..self.end)
+    }
+    fn usize_range(self) -> Self::UsizeRange {
+        self.start.into_usize()..self.end.into_usize()
+    }
+    fn canonicalize(self, _len: usize) -> Range<usize> {
+        self.usize_range()
+    }
+}
+
+/// Serialized as a `(start, end)` tuple.
+#[cfg(feature = "serde")]
+impl<I: Serialize> Serialize for Span<I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.start, &self.end).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: Deserialize<'de>> Deserialize<'de> for Span<I> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (start, end) = <(I, I)>::deserialize(deserializer)?;
+        Ok(Span { start, end })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IndexStr, Span};
+
+    #[test]
+    fn typed_offsets() {
+        let s = IndexStr::<u32>::new("aé b");
+        assert!(
+            s.char_indices_enumerated()
+                .eq([(0, 'a'), (1, 'é'), (3, ' '), (4, 'b')])
+        );
+        assert_eq!(s.find_char(char::is_whitespace), Some(3));
+        assert_eq!(s.get(1..2), None);
+        assert_eq!(s.get(Span::new(1, 3)).unwrap(), "é");
+        assert_eq!(s[3..], " b");
+        assert!(s.split_at_checked(2).is_none());
+        assert_eq!(Span::new(1, 3).cover(Span::new(0, 2)), Span::new(0, 3));
+    }
+}
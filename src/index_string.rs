@@ -0,0 +1,380 @@
+//! An owned [`String`] addressed by typed byte offsets, see [`IndexStr`].
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, IndexString};
+//!
+//! #[derive(Idx)]
+//! struct ByteOffset(u32);
+//!
+//! let mut src = IndexString::<ByteOffset>::from("fn main() {");
+//! let body = src.push_str_get_idx(" }");
+//! assert_eq!(body, ByteOffset(11));
+//! assert_eq!(&src[body..], " }");
+//! assert_eq!(String::from(src), "fn main() { }");
+//! ```
+
+use core::{
+    borrow::{Borrow, BorrowMut},
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use alloc::{borrow::Cow, boxed::Box, string::String};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{idx::Idx, index_str::IndexStr};
+
+#[repr(transparent)]
+pub struct IndexString<I> {
+    data: String,
+    _phantom: PhantomData<fn(I) -> u8>,
+}
+
+impl<I> IndexString<I> {
+    pub const fn new() -> Self {
+        Self {
+            data: String::new(),
+            _phantom: PhantomData,
+        }
+    }
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::from(String::with_capacity(cap))
+    }
+    pub fn as_index_str(&self) -> &IndexStr<I> {
+        IndexStr::new(&self.data)
+    }
+    pub fn as_mut_index_str(&mut self) -> &mut IndexStr<I> {
+        IndexStr::from_mut_str(&mut self.data)
+    }
+    pub fn as_string(&self) -> &String {
+        &self.data
+    }
+    pub fn as_mut_string(&mut self) -> &mut String {
+        &mut self.data
+    }
+    pub fn into_string(self) -> String {
+        self.data
+    }
+    pub fn into_boxed_index_str(self) -> Box<IndexStr<I>> {
+        IndexStr::from_boxed_str(self.data.into_boxed_str())
+    }
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    pub fn push(&mut self, c: char) {
+        self.data.push(c);
+    }
+    pub fn push_str(&mut self, s: &str) {
+        self.data.push_str(s);
+    }
+    /// Like [`push_str`](Self::push_str), but returns the byte offset the
+    /// string was inserted at.
+    pub fn push_str_get_idx(&mut self, s: &str) -> I
+    where
+        I: Idx,
+    {
+        let idx = self.len_idx();
+        self.data.push_str(s);
+        idx
+    }
+    pub fn pop(&mut self) -> Option<char> {
+        self.data.pop()
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn insert(&mut self, idx: I, c: char)
+    where
+        I: Idx,
+    {
+        self.data.insert(idx.into_usize(), c);
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn insert_str(&mut self, idx: I, s: &str)
+    where
+        I: Idx,
+    {
+        self.data.insert_str(idx.into_usize(), s);
+    }
+    /// # Panics
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn remove(&mut self, idx: I) -> char
+    where
+        I: Idx,
+    {
+        self.data.remove(idx.into_usize())
+    }
+    /// # Panics
+    /// Panics if `end` is not on a char boundary.
+    pub fn truncate(&mut self, end: I)
+    where
+        I: Idx,
+    {
+        self.data.truncate(end.into_usize());
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<I> Deref for IndexString<I> {
+    type Target = IndexStr<I>;
+
+    fn deref(&self) -> &IndexStr<I> {
+        self.as_index_str()
+    }
+}
+
+impl<I> DerefMut for IndexString<I> {
+    fn deref_mut(&mut self) -> &mut IndexStr<I> {
+        self.as_mut_index_str()
+    }
+}
+
+impl<I> Default for IndexString<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Clone for IndexString<I> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.clone())
+    }
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<I> Debug for IndexString<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+
+impl<I> Display for IndexString<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.data, f)
+    }
+}
+
+impl<I> core::fmt::Write for IndexString<I> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.data.write_str(s)
+    }
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.data.write_char(c)
+    }
+}
+
+impl<I> From<String> for IndexString<I> {
+    fn from(value: String) -> Self {
+        Self {
+            data: value,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I> From<&str> for IndexString<I> {
+    fn from(value: &str) -> Self {
+        Self::from(String::from(value))
+    }
+}
+
+impl<I> From<&IndexStr<I>> for IndexString<I> {
+    fn from(value: &IndexStr<I>) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl<I> From<Box<IndexStr<I>>> for IndexString<I> {
+    fn from(value: Box<IndexStr<I>>) -> Self {
+        Self::from(String::from(value.into_boxed_str()))
+    }
+}
+
+impl<'a, I> From<Cow<'a, IndexStr<I>>> for IndexString<I> {
+    fn from(value: Cow<'a, IndexStr<I>>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl<I> From<IndexString<I>> for String {
+    fn from(value: IndexString<I>) -> Self {
+        value.data
+    }
+}
+
+impl<I> FromIterator<char> for IndexString<I> {
+    fn from_iter<It: IntoIterator<Item = char>>(iter: It) -> Self {
+        Self::from(String::from_iter(iter))
+    }
+}
+
+impl<'a, I> FromIterator<&'a str> for IndexString<I> {
+    fn from_iter<It: IntoIterator<Item = &'a str>>(iter: It) -> Self {
+        Self::from(String::from_iter(iter))
+    }
+}
+
+impl<I> Extend<char> for IndexString<I> {
+    fn extend<It: IntoIterator<Item = char>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, I> Extend<&'a str> for IndexString<I> {
+    fn extend<It: IntoIterator<Item = &'a str>>(&mut self, iter: It) {
+        self.data.extend(iter);
+    }
+}
+
+impl<I> AsRef<str> for IndexString<I> {
+    fn as_ref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<I> AsRef<IndexStr<I>> for IndexString<I> {
+    fn as_ref(&self) -> &IndexStr<I> {
+        self.as_index_str()
+    }
+}
+
+impl<I> AsMut<IndexStr<I>> for IndexString<I> {
+    fn as_mut(&mut self) -> &mut IndexStr<I> {
+        self.as_mut_index_str()
+    }
+}
+
+impl<I> Borrow<IndexStr<I>> for IndexString<I> {
+    fn borrow(&self) -> &IndexStr<I> {
+        self.as_index_str()
+    }
+}
+
+impl<I> BorrowMut<IndexStr<I>> for IndexString<I> {
+    fn borrow_mut(&mut self) -> &mut IndexStr<I> {
+        self.as_mut_index_str()
+    }
+}
+
+impl<I> PartialEq for IndexString<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<I> PartialEq<str> for IndexString<I> {
+    fn eq(&self, other: &str) -> bool {
+        self.data == other
+    }
+}
+
+impl<I> PartialEq<&str> for IndexString<I> {
+    fn eq(&self, other: &&str) -> bool {
+        self.data == *other
+    }
+}
+
+impl<I> PartialEq<String> for IndexString<I> {
+    fn eq(&self, other: &String) -> bool {
+        self.data == *other
+    }
+}
+
+impl<I> PartialEq<IndexStr<I>> for IndexString<I> {
+    fn eq(&self, other: &IndexStr<I>) -> bool {
+        self.data == other.as_str()
+    }
+}
+
+impl<I> PartialEq<IndexString<I>> for str {
+    fn eq(&self, other: &IndexString<I>) -> bool {
+        self == other.data
+    }
+}
+
+impl<I> PartialEq<IndexString<I>> for &str {
+    fn eq(&self, other: &IndexString<I>) -> bool {
+        *self == other.data
+    }
+}
+
+impl<I> PartialEq<IndexString<I>> for String {
+    fn eq(&self, other: &IndexString<I>) -> bool {
+        *self == other.data
+    }
+}
+
+impl<I> Eq for IndexString<I> {}
+
+impl<I> PartialOrd for IndexString<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I> Ord for IndexString<I> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl<I> Hash for IndexString<I> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I> Serialize for IndexString<I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.data)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I> Deserialize<'de> for IndexString<I> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+
+    use crate::{IndexStr, IndexString};
+
+    #[test]
+    fn owned_roundtrip() {
+        let s: IndexString<u32> = IndexStr::new("ab").to_owned();
+        let mut s = s;
+        s.insert(1, 'é');
+        assert_eq!(s.find("b"), Some(3));
+        assert_eq!(s.remove(1), 'é');
+        assert_eq!(s, "ab");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let s = IndexString::<u32>::from("token");
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, "\"token\"");
+        assert_eq!(serde_json::from_str::<IndexString<u32>>(&json).unwrap(), s);
+    }
+}
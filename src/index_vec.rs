@@ -234,6 +234,9 @@ impl<I, T> IndexVec<I, T> {
         self.data.dedup_by(same_bucket);
     }
 
+    /// Unlike [`push_get_idx`](Self::push_get_idx), this does not check
+    /// that the new element's index is within [`Idx::MAX_USIZE`].
+    /// The same holds for all other methods that grow the vector.
     pub fn push(&mut self, v: T) {
         self.data.push(v);
     }
@@ -288,6 +291,20 @@ impl<I, T> IndexVec<I, T> {
         self.data.resize_with(new_len, f);
     }
 
+    /// Like [`IndexMut::index_mut`], but grows the vec using
+    /// [`Default::default`] if `index` is out of bounds.
+    pub fn index_grow_mut(&mut self, index: I) -> &mut T
+    where
+        I: Idx,
+        T: Default,
+    {
+        let index = index.into_usize();
+        if index >= self.data.len() {
+            self.data.resize_with(index + 1, T::default);
+        }
+        &mut self.data[index]
+    }
+
     pub fn leak<'a>(self) -> &'a mut IndexSlice<I, T> {
         self.data.leak().into()
     }
@@ -355,10 +372,17 @@ impl<I, T> IndexVec<I, T> {
         &mut self.data
     }
 
+    /// # Panics
+    /// Panics if the new element's index would exceed [`Idx::MAX_USIZE`],
+    /// regardless of the index type's bounds checking mode.
     pub fn push_get_idx(&mut self, v: T) -> I
     where
         I: Idx,
     {
+        assert!(
+            self.data.len() <= I::MAX_USIZE,
+            "IndexVec length exceeds the range of its index type"
+        );
         let id = self.len_idx();
         self.data.push(v);
         id
@@ -33,10 +33,15 @@ pub mod index_enumerate;
 
 pub mod identity_hasher;
 pub mod index_slice;
+
+pub mod index_str;
 pub mod sequence;
 
 pub mod index_array;
 
+pub mod index_array_deque;
+pub mod index_array_slab;
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub mod index_vec;
@@ -45,6 +50,54 @@ pub mod index_vec;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub mod index_vec_deque;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_seq_queue;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_string;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod line_index;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_stamp_vec;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_once_vec;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_default_vec;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod packed_idx_vec;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_sorted_vec_map;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_range_set;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod index_graph;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod algo;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod dot;
+
 #[cfg(feature = "arrayvec")]
 #[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
 pub mod index_array_vec;
@@ -65,16 +118,36 @@ pub mod index_hash_set;
 #[cfg_attr(docsrs, doc(cfg(feature = "slab")))]
 pub mod index_slab;
 
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod index_heapless_vec;
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod index_heapless_deque;
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod index_heapless_map;
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod index_heapless_string;
+
 #[cfg(feature = "nonmax")]
 #[cfg_attr(docsrs, doc(cfg(feature = "nonmax")))]
 pub mod nonmax;
 
+pub mod bounded_idx;
+
+pub mod typed_id;
+
 // convenience exports
 
 // traits
 #[doc(inline)]
 pub use crate::{
-    idx::{ArithCompat, Idx, IdxCompat, IdxEnum, IdxNewtype},
+    idx::{ArithCompat, Idx, IdxCompat, IdxDelta, IdxEnum, IdxNewtype},
     index_range::IndexRangeBounds,
 };
 
@@ -82,12 +155,24 @@ pub use crate::{
 #[doc(inline)]
 pub use crate::index_range::{IndexRange, IndexRangeFrom, IndexRangeInclusive};
 
+#[doc(inline)]
+pub use crate::idx::{IdxOutOfRange, ParseIdxEnumError};
+
 #[doc(inline)]
 pub use index_slice::IndexSlice;
 
+#[doc(inline)]
+pub use index_str::{IndexStr, Span};
+
 #[doc(inline)]
 pub use index_array::IndexArray;
 
+#[doc(inline)]
+pub use index_array_slab::IndexArraySlab;
+
+#[doc(inline)]
+pub use index_array_deque::IndexArrayDeque;
+
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use index_vec::IndexVec;
@@ -96,6 +181,50 @@ pub use index_vec::IndexVec;
 #[doc(inline)]
 pub use index_vec_deque::IndexVecDeque;
 
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_seq_queue::IndexSeqQueue;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_string::IndexString;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use line_index::LineIndex;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_stamp_vec::IndexStampVec;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_once_vec::IndexOnceVec;
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use index_once_vec::IndexOnceLockVec;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_default_vec::IndexDefaultVec;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use packed_idx_vec::PackedIdxVec;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_sorted_vec_map::IndexSortedVecMap;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_range_set::IndexRangeSet;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use index_graph::IndexGraph;
+
 #[cfg(feature = "derive")]
 extern crate indexland_derive;
 
@@ -108,6 +237,12 @@ pub use indexland_derive::{Idx, IdxEnum, IdxNewtype};
 #[doc(inline)]
 pub use nonmax::NonMax;
 
+#[doc(inline)]
+pub use bounded_idx::BoundedIdx;
+
+#[doc(inline)]
+pub use typed_id::TypedId;
+
 #[cfg(feature = "arrayvec")]
 #[doc(inline)]
 pub use index_array_vec::IndexArrayVec;
@@ -120,6 +255,13 @@ pub use index_small_vec::IndexSmallVec;
 #[doc(inline)]
 pub use {index_hash_map::IndexHashMap, index_hash_set::IndexHashSet};
 
+#[cfg(feature = "heapless")]
+#[doc(inline)]
+pub use {
+    index_heapless_deque::IndexHeaplessDeque, index_heapless_map::IndexHeaplessMap,
+    index_heapless_string::IndexHeaplessString, index_heapless_vec::IndexHeaplessVec,
+};
+
 pub use identity_hasher::IdentityHasher;
 
 // type aliases
@@ -139,6 +281,10 @@ pub use smallvec;
 #[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
 pub use indexmap;
 
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub use heapless;
+
 // used in macros, not public api
 #[doc(hidden)]
 pub mod __private {
@@ -146,6 +292,9 @@ pub mod __private {
 
     use crate::{Idx, IndexArray};
 
+    #[cfg(feature = "serde")]
+    pub use serde;
+
     /// Essentially [`std::mem::MaybeUninit::transpose`] in stable Rust. Will
     /// be removed once [maybe_uninit_uninit_array_transpose](https://github.com/rust-lang/rust/issues/96097)
     /// is stabilized.
@@ -247,7 +396,8 @@ pub mod __private {
     }
 
     // NOTE: this is unfortunately not const because `Idx::into_usize` is
-    // a trait method :(.
+    // a trait method :(. `index_array![I; ..]` uses the generated
+    // `into_usize_const` instead.
     #[track_caller]
     pub fn index_array_from_values_and_distinct_indices<I, T, const N: usize>(
         indices: [I; N],
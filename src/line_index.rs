@@ -0,0 +1,206 @@
+//! Mapping between typed byte offsets and line / column positions in text.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, LineIndex};
+//!
+//! #[derive(Idx)]
+//! struct LineId(u32);
+//!
+//! #[derive(Idx)]
+//! struct ByteOffset(u32);
+//!
+//! let text = "fn main() {\n    let π = 3;\n}\n";
+//! let mut lines = LineIndex::<LineId, ByteOffset>::new(text);
+//!
+//! let three = ByteOffset(text.find('3').unwrap() as u32);
+//! assert_eq!(lines.line_col(three), (LineId(1), 13));
+//! assert_eq!(lines.line_col_utf16(three), (LineId(1), 12));
+//! let last = lines.line_range(LineId(2));
+//! assert_eq!((last.start, last.end), (ByteOffset(28), ByteOffset(30)));
+//!
+//! // insert a line at the start of the file
+//! lines.apply_edit(ByteOffset(0)..ByteOffset(0), "// hi\n");
+//! assert_eq!(lines.line_of(ByteOffset(6)), LineId(1));
+//! ```
+
+use core::{marker::PhantomData, ops::Range};
+
+use alloc::vec::Vec;
+
+use crate::{IndexRange, IndexRangeBounds, IndexSlice, IndexVec};
+
+use super::idx::Idx;
+
+/// A char that takes up more than one byte in UTF-8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct WideChar {
+    offset: usize,
+    // how many fewer UTF-16 code units than UTF-8 bytes it takes up
+    utf16_shrink: usize,
+}
+
+/// The byte offsets at which each line of a text starts, addressed by the
+/// line index `L`.
+///
+/// Lines are terminated by `'\n'`, the terminator belongs to the line it
+/// ends. A text ending in `'\n'` has an empty last line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineIndex<L, B> {
+    line_starts: IndexVec<L, B>,
+    wide_chars: Vec<WideChar>,
+    len: usize,
+    _phantom: PhantomData<fn(B) -> L>,
+}
+
+impl<L: Idx, B: Idx> LineIndex<L, B> {
+    pub fn new(text: &str) -> Self {
+        let mut res = Self {
+            line_starts: IndexVec::from(alloc::vec![B::ZERO]),
+            wide_chars: Vec::new(),
+            len: 0,
+            _phantom: PhantomData,
+        };
+        res.apply_edit(B::ZERO..B::ZERO, text);
+        res
+    }
+
+    pub fn line_starts(&self) -> &IndexSlice<L, B> {
+        &self.line_starts
+    }
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+    /// The length of the indexed text.
+    pub fn len_idx(&self) -> B {
+        B::from_usize(self.len)
+    }
+
+    /// The line containing `offset`. Offsets past the end of the text map to
+    /// the last line.
+    pub fn line_of(&self, offset: B) -> L {
+        let offset = offset.into_usize();
+        let next = self
+            .line_starts
+            .partition_point(|s| s.into_usize() <= offset);
+        L::from_usize(next.into_usize() - 1)
+    }
+    /// The line of `offset` and its column in bytes.
+    pub fn line_col(&self, offset: B) -> (L, usize) {
+        let line = self.line_of(offset);
+        let col = offset.into_usize() - self.line_starts[line].into_usize();
+        (line, col)
+    }
+    /// The line of `offset` and its column in UTF-16 code units, as used by
+    /// e.g. the Language Server Protocol.
+    pub fn line_col_utf16(&self, offset: B) -> (L, usize) {
+        let (line, col) = self.line_col(offset);
+        let start = self.line_starts[line].into_usize();
+        let offset = offset.into_usize();
+        let lo = self.wide_chars.partition_point(|c| c.offset < start);
+        let hi = self.wide_chars.partition_point(|c| c.offset < offset);
+        let shrink = self.wide_chars[lo..hi]
+            .iter()
+            .map(|c| c.utf16_shrink)
+            .sum::<usize>();
+        (line, col - shrink)
+    }
+    /// The byte offset of `col` in `line`, or `None` if the line is too
+    /// short or does not exist.
+    pub fn offset(&self, line: L, col: usize) -> Option<B> {
+        let range = self.try_line_range(line)?;
+        let offset = range.start.into_usize() + col;
+        (offset <= range.end.into_usize()).then(|| B::from_usize(offset))
+    }
+
+    /// The bytes of `line`, including its terminator.
+    ///
+    /// # Panics
+    /// Panics if `line` is out of bounds.
+    pub fn line_range(&self, line: L) -> IndexRange<B> {
+        self.try_line_range(line)
+            .expect("LineIndex line out of bounds")
+    }
+    pub fn try_line_range(&self, line: L) -> Option<IndexRange<B>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(L::from_usize(line.into_usize() + 1))
+            .copied()
+            .unwrap_or(B::from_usize(self.len));
+        Some(IndexRange::new(start..end))
+    }
+
+    /// Updates the index after the bytes in `range` were replaced with
+    /// `new_text`, without rescanning the rest of the text.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    pub fn apply_edit<R: IndexRangeBounds<B>>(&mut self, range: R, new_text: &str) {
+        let Range { start, end } = range.canonicalize(self.len);
+        assert!(
+            start <= end && end <= self.len,
+            "LineIndex edit range out of bounds"
+        );
+        let removed = end - start;
+        let added = new_text.len();
+
+        // starts that follow a `'\n'` within the replaced range
+        let lo = self
+            .line_starts
+            .partition_point(|s| s.into_usize() <= start);
+        let hi = self.line_starts.partition_point(|s| s.into_usize() <= end);
+        let new_starts = new_text
+            .match_indices('\n')
+            .map(|(i, _)| B::from_usize(start + i + 1));
+        let _ = self.line_starts.splice(lo..hi, new_starts);
+        let first_shifted = lo.into_usize() + new_text.matches('\n').count();
+        for s in &mut self.line_starts.as_mut_slice()[first_shifted..] {
+            *s = B::from_usize(s.into_usize() - removed + added);
+        }
+
+        let lo = self.wide_chars.partition_point(|c| c.offset < start);
+        let hi = self.wide_chars.partition_point(|c| c.offset < end);
+        let new_wide = new_text
+            .char_indices()
+            .filter(|(_, c)| !c.is_ascii())
+            .map(|(i, c)| WideChar {
+                offset: start + i,
+                utf16_shrink: c.len_utf8() - c.len_utf16(),
+            });
+        let _ = self.wide_chars.splice(lo..hi, new_wide);
+        let first_shifted = lo + new_text.chars().filter(|c| !c.is_ascii()).count();
+        for c in &mut self.wide_chars[first_shifted..] {
+            c.offset = c.offset - removed + added;
+        }
+
+        self.len = self.len - removed + added;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LineIndex;
+
+    #[test]
+    fn utf16_columns() {
+        let lines = LineIndex::<u32, u32>::new("a\n😀é=1");
+        assert_eq!(lines.line_count(), 2);
+        assert_eq!(lines.line_col(8), (1, 6));
+        assert_eq!(lines.line_col_utf16(8), (1, 3));
+        assert_eq!(lines.line_col(1), (0, 1));
+        assert_eq!(lines.offset(1, 7), Some(9));
+        assert_eq!(lines.offset(0, 3), None);
+    }
+
+    #[test]
+    fn incremental_edits_match_rebuild() {
+        let mut text = alloc::string::String::from("one\ntwö\nthree\n");
+        let mut lines = LineIndex::<u32, usize>::new(&text);
+        for (range, new_text) in [(2..9, "ñ\n\nx"), (0..0, "\n"), (5..7, "")] {
+            text.replace_range(range.clone(), new_text);
+            lines.apply_edit(range, new_text);
+            assert_eq!(lines, LineIndex::new(&text));
+        }
+    }
+}
@@ -34,7 +34,7 @@ use core::convert::TryFrom;
 #[cfg(any(not(debug_assertions), feature = "disable_debuggable_nonmax"))]
 use core::num::NonZero;
 
-use crate::Idx;
+use crate::{Idx, IdxOutOfRange};
 
 /// An Integer value that's dynamically guaranteed to never be MAX. This enables
 /// [Niche Layout Optimizations](https://doc.rust-lang.org/std/option/index.html#representation),
@@ -334,6 +334,10 @@ macro_rules! impl_nonmax_idx {
             fn into_usize(self) -> usize {
                 usize::try_from(self.get()).unwrap()
             }
+            #[inline]
+            fn try_into_usize(self) -> Option<usize> {
+                usize::try_from(self.get()).ok()
+            }
             #[inline(always)]
             fn from_usize_unchecked(v: usize) -> Self {
                 #![allow(
@@ -364,6 +368,9 @@ macro_rules! impl_nonmax_idx {
             fn saturating_sub(self, other: Self) -> Self {
                 NonMax::<$primitive>::saturating_sub(self, other)
             }
+            fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+                NonMax::<$primitive>::try_from(v).map_err(|_| IdxOutOfRange::new::<Self>(v))
+            }
         }
     )*};
 }
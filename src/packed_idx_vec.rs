@@ -0,0 +1,506 @@
+//! A bit-packed vector of indices.
+//!
+//! [`PackedIdxVec<I, J>`] stores each `J` in only as many bits as are needed
+//! to represent `J::MAX_USIZE`. This is useful for tables of small indices,
+//! e.g. an [`IdxEnum`](crate::IdxEnum) or a 20 bit id, where storing every
+//! element in a `u32` would waste most of the memory.
+//!
+//! In variable width mode (see [`PackedIdxVec::new_variable_width`]) the
+//! width starts at zero bits and the vector is repacked whenever a value that
+//! exceeds the current width is stored.
+//!
+//! ## Example
+//! ```
+//! use indexland::{Idx, PackedIdxVec};
+//!
+//! #[derive(Idx)]
+//! struct NodeId(u32);
+//!
+//! #[derive(Idx)]
+//! enum Color {
+//!     Red,
+//!     Green,
+//!     Blue,
+//! }
+//!
+//! let mut colors = PackedIdxVec::<NodeId, Color>::new();
+//! assert_eq!(colors.bits_per_value(), 2);
+//!
+//! let id = colors.push(Color::Green);
+//! colors.set(id, Color::Blue);
+//! assert_eq!(colors.get(id), Color::Blue);
+//! ```
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Idx, IndexVec, index_enumerate::IndexEnumerate};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The number of bits needed to represent all values up to `max`.
+const fn bits_needed(max: usize) -> u32 {
+    usize::BITS - max.leading_zeros()
+}
+
+const fn word_count(len: usize, bits: u32) -> usize {
+    (len * bits as usize).div_ceil(WORD_BITS)
+}
+
+#[inline]
+fn mask(bits: u32) -> u64 {
+    if bits == u64::BITS {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+#[inline]
+fn read_bits(words: &[u64], offset: usize, bits: u32) -> usize {
+    if bits == 0 {
+        return 0;
+    }
+    let word = offset / WORD_BITS;
+    let shift = offset % WORD_BITS;
+    let mut value = words[word] >> shift;
+    if shift + bits as usize > WORD_BITS {
+        value |= words[word + 1] << (WORD_BITS - shift);
+    }
+    // values are never wider than a usize
+    #[allow(clippy::cast_possible_truncation)]
+    let value = (value & mask(bits)) as usize;
+    value
+}
+
+#[inline]
+fn write_bits(words: &mut [u64], offset: usize, bits: u32, value: usize) {
+    if bits == 0 {
+        return;
+    }
+    let value = value as u64;
+    let mask = mask(bits);
+    let word = offset / WORD_BITS;
+    let shift = offset % WORD_BITS;
+    words[word] = (words[word] & !(mask << shift)) | (value << shift);
+    if shift + bits as usize > WORD_BITS {
+        let written = WORD_BITS - shift;
+        words[word + 1] = (words[word + 1] & !(mask >> written)) | (value >> written);
+    }
+}
+
+pub struct PackedIdxVec<I, J> {
+    words: Vec<u64>,
+    len: usize,
+    bits: u32,
+    _phantom: PhantomData<fn(I) -> J>,
+}
+
+impl<I, J> PackedIdxVec<I, J> {
+    /// Creates a vector that stores every value in
+    /// `ceil(log2(J::MAX_USIZE + 1))` bits.
+    pub const fn new() -> Self
+    where
+        J: Idx,
+    {
+        Self::with_bits_per_value(bits_needed(J::MAX_USIZE))
+    }
+
+    /// Creates a vector that starts out at a width of zero bits and is
+    /// repacked to a larger width whenever a value does not fit.
+    pub const fn new_variable_width() -> Self {
+        Self::with_bits_per_value(0)
+    }
+
+    const fn with_bits_per_value(bits: u32) -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+            bits,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self
+    where
+        J: Idx,
+    {
+        let mut res = Self::new();
+        res.words.reserve(word_count(cap, res.bits));
+        res
+    }
+
+    /// The number of bits currently used to store each value.
+    pub fn bits_per_value(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn len_idx(&self) -> I
+    where
+        I: Idx,
+    {
+        I::from_usize(self.len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.words.shrink_to_fit();
+    }
+
+    pub fn get(&self, idx: I) -> J
+    where
+        I: Idx,
+        J: Idx,
+    {
+        let index = idx.into_usize();
+        assert!(
+            index < self.len,
+            "index out of bounds: the len is {} but the index is {index}",
+            self.len
+        );
+        J::from_usize_unchecked(read_bits(
+            &self.words,
+            index * self.bits as usize,
+            self.bits,
+        ))
+    }
+
+    pub fn set(&mut self, idx: I, value: J)
+    where
+        I: Idx,
+        J: Idx,
+    {
+        let index = idx.into_usize();
+        assert!(
+            index < self.len,
+            "index out of bounds: the len is {} but the index is {index}",
+            self.len
+        );
+        let value = value.into_usize();
+        self.fit_value(value);
+        write_bits(
+            &mut self.words,
+            index * self.bits as usize,
+            self.bits,
+            value,
+        );
+    }
+
+    pub fn push(&mut self, value: J) -> I
+    where
+        I: Idx,
+        J: Idx,
+    {
+        let index = self.len;
+        let value = value.into_usize();
+        self.fit_value(value);
+        self.len += 1;
+        self.words.resize(word_count(self.len, self.bits), 0);
+        write_bits(
+            &mut self.words,
+            index * self.bits as usize,
+            self.bits,
+            value,
+        );
+        I::from_usize(index)
+    }
+
+    pub fn pop(&mut self) -> Option<J>
+    where
+        J: Idx,
+    {
+        let index = self.len.checked_sub(1)?;
+        let value = read_bits(&self.words, index * self.bits as usize, self.bits);
+        // clear the bits so that equal contents produce equal words
+        write_bits(&mut self.words, index * self.bits as usize, self.bits, 0);
+        self.len = index;
+        self.words.truncate(word_count(self.len, self.bits));
+        Some(J::from_usize_unchecked(value))
+    }
+
+    /// Repacks the vector if `value` does not fit into the current width.
+    fn fit_value(&mut self, value: usize) {
+        let bits = bits_needed(value);
+        if bits > self.bits {
+            self.repack(bits);
+        }
+    }
+
+    fn repack(&mut self, bits: u32) {
+        let mut words = Vec::with_capacity(word_count(self.len, bits).max(self.words.capacity()));
+        words.resize(word_count(self.len, bits), 0);
+        for i in 0..self.len {
+            let value = read_bits(&self.words, i * self.bits as usize, self.bits);
+            write_bits(&mut words, i * bits as usize, bits, value);
+        }
+        self.words = words;
+        self.bits = bits;
+    }
+
+    pub fn iter(&self) -> Iter<'_, J>
+    where
+        J: Idx,
+    {
+        Iter {
+            words: &self.words,
+            bits: self.bits,
+            pos: 0,
+            end: self.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn iter_enumerated(&self) -> IndexEnumerate<I, Iter<'_, J>>
+    where
+        I: Idx,
+        J: Idx,
+    {
+        IndexEnumerate::new(I::ZERO, self.iter())
+    }
+
+    /// Decodes all values into an [`IndexVec`].
+    pub fn to_index_vec(&self) -> IndexVec<I, J>
+    where
+        J: Idx,
+    {
+        IndexVec::from(self.iter().collect::<Vec<_>>())
+    }
+}
+
+// ===== Iter =====
+
+#[derive(Clone)]
+pub struct Iter<'a, J> {
+    words: &'a [u64],
+    bits: u32,
+    pos: usize,
+    end: usize,
+    _phantom: PhantomData<fn() -> J>,
+}
+
+impl<J: Idx> Iterator for Iter<'_, J> {
+    type Item = J;
+
+    fn next(&mut self) -> Option<J> {
+        if self.pos == self.end {
+            return None;
+        }
+        let value = read_bits(self.words, self.pos * self.bits as usize, self.bits);
+        self.pos += 1;
+        Some(J::from_usize_unchecked(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<J: Idx> DoubleEndedIterator for Iter<'_, J> {
+    fn next_back(&mut self) -> Option<J> {
+        if self.pos == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let value = read_bits(self.words, self.end * self.bits as usize, self.bits);
+        Some(J::from_usize_unchecked(value))
+    }
+}
+
+impl<J: Idx> ExactSizeIterator for Iter<'_, J> {}
+
+impl<J: Idx> core::iter::FusedIterator for Iter<'_, J> {}
+
+impl<I, J: Idx> Default for PackedIdxVec<I, J> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, J> Clone for PackedIdxVec<I, J> {
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.clone(),
+            len: self.len,
+            bits: self.bits,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, J: Idx + Debug> Debug for PackedIdxVec<I, J> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<I, J: Idx + PartialEq> PartialEq for PackedIdxVec<I, J> {
+    fn eq(&self, other: &Self) -> bool {
+        // the widths may differ in variable width mode
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<I, J: Idx + Eq> Eq for PackedIdxVec<I, J> {}
+
+impl<I: Idx, J: Idx> Extend<J> for PackedIdxVec<I, J> {
+    fn extend<It: IntoIterator<Item = J>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        self.words
+            .reserve(word_count(self.len + iter.size_hint().0, self.bits) - self.words.len());
+        for value in iter {
+            let _ = self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, J: Idx> FromIterator<J> for PackedIdxVec<I, J> {
+    fn from_iter<It: IntoIterator<Item = J>>(iter: It) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<I: Idx, J: Idx> From<&IndexVec<I, J>> for PackedIdxVec<I, J> {
+    fn from(value: &IndexVec<I, J>) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl<I, J: Idx> From<&PackedIdxVec<I, J>> for IndexVec<I, J> {
+    fn from(value: &PackedIdxVec<I, J>) -> Self {
+        value.to_index_vec()
+    }
+}
+
+impl<'a, I, J: Idx> IntoIterator for &'a PackedIdxVec<I, J> {
+    type Item = J;
+
+    type IntoIter = Iter<'a, J>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serialized in the packed form as a `(bits_per_value, len, words)` tuple.
+#[cfg(feature = "serde")]
+impl<I, J> Serialize for PackedIdxVec<I, J> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.bits, self.len, &self.words).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, J: Idx> Deserialize<'de> for PackedIdxVec<I, J> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let (bits, len, words) = <(u32, usize, Vec<u64>)>::deserialize(deserializer)?;
+        let max_bits = bits_needed(J::MAX_USIZE);
+        if bits > max_bits {
+            return Err(D::Error::custom(format_args!(
+                "packed width of {bits} bits exceeds the maximum of {max_bits} bits"
+            )));
+        }
+        if len.checked_mul(bits as usize).is_none() || words.len() != word_count(len, bits) {
+            return Err(D::Error::invalid_length(
+                words.len(),
+                &"a word count matching the packed length",
+            ));
+        }
+        let res = Self {
+            words,
+            len,
+            bits,
+            _phantom: PhantomData,
+        };
+        // values of full width may still exceed `J::MAX`
+        if bits == max_bits {
+            for i in 0..len {
+                let value = read_bits(&res.words, i * bits as usize, bits);
+                if value > J::MAX_USIZE {
+                    return Err(D::Error::custom(format_args!(
+                        "packed value {value} exceeds the maximum of {}",
+                        J::MAX_USIZE
+                    )));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PackedIdxVec, bits_needed};
+
+    #[test]
+    fn bit_widths() {
+        assert_eq!(bits_needed(0), 0);
+        assert_eq!(bits_needed(1), 1);
+        assert_eq!(bits_needed(2), 2);
+        assert_eq!(bits_needed(255), 8);
+        assert_eq!(bits_needed(usize::MAX), usize::BITS);
+        assert_eq!(PackedIdxVec::<u32, u8>::new().bits_per_value(), 8);
+    }
+
+    #[test]
+    fn variable_width_repacks() {
+        let mut v = PackedIdxVec::<usize, usize>::new_variable_width();
+        for i in 0..100 {
+            assert_eq!(v.push(i % 3), i);
+        }
+        assert_eq!(v.bits_per_value(), 2);
+        v.set(50, 1 << 40);
+        assert_eq!(v.bits_per_value(), 41);
+        assert_eq!(v.get(50), 1 << 40);
+        assert_eq!(v.get(49), 49 % 3);
+        assert_eq!(v.get(99), 0);
+        let _ = v.push(usize::MAX);
+        assert_eq!(v.bits_per_value(), usize::BITS);
+        assert_eq!(v.pop(), Some(usize::MAX));
+
+        let decoded = v.to_index_vec();
+        assert_eq!(decoded.len(), 100);
+        assert!(v.iter().eq(decoded.iter().copied()));
+        assert_eq!(PackedIdxVec::from(&decoded), v);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_packed() {
+        let v = PackedIdxVec::<u32, u8>::from_iter([1, 2, 3]);
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, "[8,3,[197121]]");
+        assert_eq!(
+            serde_json::from_str::<PackedIdxVec<u32, u8>>(&json).unwrap(),
+            v
+        );
+        assert!(serde_json::from_str::<PackedIdxVec<u32, u8>>("[9,3,[197121]]").is_err());
+        assert!(serde_json::from_str::<PackedIdxVec<u32, u8>>("[8,3,[]]").is_err());
+    }
+}
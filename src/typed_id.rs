@@ -0,0 +1,197 @@
+//! Ready-made generic id type, parameterized by the type it identifies.
+//!
+//! This avoids declaring one newtype per id kind when all of them share
+//! the same representation.
+//!
+//! ## Example
+//! ```
+//! use indexland::{IndexVec, TypedId};
+//!
+//! struct Node;
+//!
+//! let mut nodes: IndexVec<TypedId<Node>, Node> = IndexVec::new();
+//! let n = nodes.push_get_idx(Node);
+//! assert_eq!(n.into_inner(), 0u32);
+//! ```
+
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use crate::{Idx, IdxNewtype, IdxOutOfRange};
+
+/// An id of type `B` that can only be used for collections of `T`.
+///
+/// None of the trait implementations place any bounds on `T`.
+#[repr(transparent)]
+pub struct TypedId<T, B = u32>(B, PhantomData<fn() -> T>);
+
+impl<T, B> TypedId<T, B> {
+    pub const fn new(inner: B) -> Self {
+        TypedId(inner, PhantomData)
+    }
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<T, B: Copy> Clone for TypedId<T, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, B: Copy> Copy for TypedId<T, B> {}
+
+impl<T, B: Default> Default for TypedId<T, B> {
+    fn default() -> Self {
+        TypedId::new(B::default())
+    }
+}
+
+impl<T, B: PartialEq> PartialEq for TypedId<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T, B: Eq> Eq for TypedId<T, B> {}
+
+impl<T, B: PartialOrd> PartialOrd for TypedId<T, B> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T, B: Ord> Ord for TypedId<T, B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T, B: Hash> Hash for TypedId<T, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T, B: Debug> Debug for TypedId<T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T, B: Display> Display for TypedId<T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: 'static, B: Idx> Idx for TypedId<T, B> {
+    const ZERO: Self = TypedId::new(B::ZERO);
+    const ONE: Self = TypedId::new(B::ONE);
+    const MAX: Self = TypedId::new(B::MAX);
+    const MAX_USIZE: usize = B::MAX_USIZE;
+
+    #[inline(always)]
+    fn from_usize(v: usize) -> Self {
+        TypedId::new(B::from_usize(v))
+    }
+    #[inline(always)]
+    fn from_usize_unchecked(v: usize) -> Self {
+        TypedId::new(B::from_usize_unchecked(v))
+    }
+    #[inline(always)]
+    fn into_usize(self) -> usize {
+        self.0.into_usize()
+    }
+    #[inline(always)]
+    fn into_usize_unchecked(self) -> usize {
+        self.0.into_usize_unchecked()
+    }
+    #[inline(always)]
+    fn try_into_usize(self) -> Option<usize> {
+        self.0.try_into_usize()
+    }
+    fn wrapping_add(self, other: Self) -> Self {
+        TypedId::new(self.0.wrapping_add(other.0))
+    }
+    fn wrapping_sub(self, other: Self) -> Self {
+        TypedId::new(self.0.wrapping_sub(other.0))
+    }
+    fn saturating_add(self, other: Self) -> Self {
+        TypedId::new(self.0.saturating_add(other.0))
+    }
+    fn saturating_sub(self, other: Self) -> Self {
+        TypedId::new(self.0.saturating_sub(other.0))
+    }
+    fn try_from_usize(v: usize) -> Result<Self, IdxOutOfRange> {
+        B::try_from_usize(v).map(TypedId::new)
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(TypedId::new)
+    }
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(TypedId::new)
+    }
+    fn checked_offset(self, offset: isize) -> Option<Self> {
+        self.0.checked_offset(offset).map(TypedId::new)
+    }
+}
+
+impl<T: 'static, B: Idx> IdxNewtype for TypedId<T, B> {
+    type Base = B;
+    fn new(inner: B) -> Self {
+        TypedId::new(inner)
+    }
+    fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<T, B: Add<Output = B>> Add for TypedId<T, B> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        TypedId::new(self.0 + rhs.0)
+    }
+}
+
+impl<T, B: Sub<Output = B>> Sub for TypedId<T, B> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        TypedId::new(self.0 - rhs.0)
+    }
+}
+
+impl<T, B: AddAssign> AddAssign for TypedId<T, B> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl<T, B: SubAssign> SubAssign for TypedId<T, B> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Idx;
+
+    use super::TypedId;
+
+    #[test]
+    fn no_bounds_on_marker() {
+        struct Opaque;
+        type Id = TypedId<Opaque, u16>;
+        assert!(Id::ZERO < Id::ONE);
+        assert_eq!(Id::from_usize(3) + Id::ONE, Id::new(4));
+        assert_eq!(Id::MAX_USIZE, u16::MAX as usize);
+        assert!(Id::try_from_usize(1 << 16).is_err());
+    }
+}
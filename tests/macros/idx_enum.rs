@@ -0,0 +1,52 @@
+use indexland::{EnumIndexArray, Idx, IdxEnum, IndexArray, idx_enum};
+
+#[test]
+fn declarative_idx_enum() {
+    idx_enum! {
+        #![extra(Display, FromStr, Const)]
+        #![arith = "full"]
+        pub enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+        enum Rgb { R, G, B }
+    }
+
+    idx_enum! {
+        enum Unit { Only }
+    }
+
+    // `Display` is opt-in, so users may provide their own
+    impl std::fmt::Display for Unit {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("unit")
+        }
+    }
+
+    const BLUE: usize = Color::Blue.into_usize_const();
+
+    let hex: EnumIndexArray<Color, &str> = IndexArray::new(["#f00", "#0f0", "#00f"]);
+    assert_eq!(hex[Color::Blue], "#00f");
+    assert_eq!(Color::VARIANT_COUNT, 3);
+    assert_eq!(Color::MAX, Color::Blue);
+    assert_eq!(Color::default(), Color::Red);
+    assert_eq!(Color::Red + Color::Green, Color::Green);
+    assert_eq!(Color::Blue % Color::Blue, Color::Red);
+    assert_eq!(Rgb::B * Rgb::G, Rgb::B);
+    assert_eq!(Color::Blue.wrapping_add(Color::Green), Color::Red);
+    assert_eq!(Color::Red.wrapping_sub(Color::Green), Color::Blue);
+    assert!(Color::try_from_usize(3).is_err());
+    assert_eq!(Color::Green.to_string(), "Green");
+    assert_eq!("Blue".parse::<Color>(), Ok(Color::Blue));
+    assert_eq!(
+        format!("{:?}", hex.debug_map()),
+        r##"{Red: "#f00", Green: "#0f0", Blue: "#00f"}"##
+    );
+
+    assert_eq!(Color::from_usize_const(BLUE), Color::Blue);
+
+    assert_eq!(Unit::ONE, Unit::Only);
+    assert_eq!(Unit::Only.wrapping_add(Unit::Only), Unit::Only);
+    assert_eq!(Unit::Only.to_string(), "unit");
+}
@@ -1,4 +1,4 @@
-use indexland::{Idx, IndexArray, idx_newtype};
+use indexland::{Idx, IndexArray, idx_newtype, index_array};
 
 #[test]
 fn declarative_idx_newtype() {
@@ -16,3 +16,46 @@ fn declarative_idx_newtype() {
     let bar = IndexArray::<BazId, i32, 1>::new([42]);
     assert_eq!(bar[BazId::ZERO], 42);
 }
+
+#[test]
+fn idx_newtype_checked() {
+    idx_newtype! {
+        pub struct SmallId(u8);
+    }
+
+    assert_eq!(SmallId::try_from_usize(7), Ok(SmallId(7)));
+    let err = SmallId::try_from_usize(300).unwrap_err();
+    assert!(err.type_name().ends_with("SmallId"));
+    assert_eq!(err.value(), 300);
+    assert_eq!(SmallId(250).checked_add(SmallId(6)), None);
+    assert_eq!(SmallId(2).checked_offset(3), Some(SmallId(5)));
+    assert_eq!(SmallId(2).distance(SmallId(9)), 7);
+}
+
+#[test]
+fn idx_newtype_const_conversions() {
+    idx_newtype! {
+        #![extra(Const)]
+        pub struct NodeId(u16);
+    }
+
+    const TABLE: IndexArray<NodeId, &str, 2> = index_array![NodeId;
+        NodeId(1) => "b",
+        NodeId(0) => "a",
+    ];
+    const B: &str = TABLE.index_const(NodeId::from_usize_const(1).into_usize_const());
+    assert_eq!(B, "b");
+}
+
+#[cfg(feature = "nonmax")]
+#[test]
+fn idx_newtype_nonmax() {
+    use indexland::NonMax;
+
+    idx_newtype! {
+        pub struct NmId(NonMax<u32>);
+    }
+
+    assert_eq!(NmId::from_usize(3).into_usize(), 3);
+    assert!(NmId::try_from_usize(u32::MAX as usize).is_err());
+}
@@ -5,6 +5,8 @@ pub mod index_array;
 
 mod idx_newtype;
 
+mod idx_enum;
+
 #[cfg(feature = "alloc")]
 pub mod index_vec;
 